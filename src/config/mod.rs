@@ -0,0 +1,649 @@
+use axum::http::{HeaderName, Method};
+use std::{collections::HashMap, env, fmt, fs, net::SocketAddr};
+use webauthn_rs::prelude::Url;
+
+use crate::utils::origins::OriginPattern;
+
+const DEFAULT_CORS_METHODS: &str = "GET,POST,PUT,PATCH,DELETE,OPTIONS";
+const DEFAULT_CORS_HEADERS: &str = "content-type,accept,user-agent,cookie,authorization,x-requested-with,x-csrf-token";
+const DEFAULT_ROUTE_BUDGETS: &[(&str, &str)] = &[
+    ("polls.create", "10/60"),
+    ("polls.vote", "30/60"),
+    ("polls.manage", "60/60"),
+    ("orgs.manage", "20/60"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Development,
+    Staging,
+    Production,
+}
+
+impl Profile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::Development => "development",
+            Profile::Staging => "staging",
+            Profile::Production => "production",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Profile> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "dev" | "development" => Some(Profile::Development),
+            "staging" | "preview" => Some(Profile::Staging),
+            "prod" | "production" => Some(Profile::Production),
+            _ => None,
+        }
+    }
+}
+
+pub struct ServerConfig {
+    pub addr: SocketAddr,
+    pub cors_origins: Vec<OriginPattern>,
+    pub cors_allowed_methods: Vec<Method>,
+    pub cors_allowed_headers: Vec<HeaderName>,
+    pub csrf_trusted_origins: Vec<OriginPattern>,
+    /// Number of trusted reverse proxies in front of the server; 0 ignores forwarding headers.
+    pub trusted_proxy_hops: usize,
+    pub shutdown_timeout: u64,
+    /// Bearer secret required to scrape /metrics; only optional in the development profile.
+    pub metrics_token: Option<String>,
+}
+
+pub struct DatabaseConfig {
+    pub mongo_uri: String,
+    pub db_name: String,
+}
+
+pub struct WebauthnConfig {
+    pub rp_id: String,
+    pub rp_origin: Url,
+    pub extra_origins: Vec<Url>,
+    pub allow_subdomains: bool,
+    pub allow_any_port: bool,
+    pub rp_name: String,
+    pub require_user_verification: bool,
+    pub block_cloned_credentials: bool,
+    pub aaguid_allow_list: Vec<String>,
+    pub metadata_path: Option<String>,
+}
+
+pub struct JwtConfig {
+    pub keys: Vec<String>,
+    pub secret: Option<String>,
+    pub active_kid: Option<String>,
+}
+
+pub struct SessionConfig {
+    pub access_token_max_age: i64,
+    pub refresh_token_max_age: i64,
+    pub step_up_max_age: i64,
+}
+
+pub struct AuthGuardConfig {
+    pub decoy_key: Option<String>,
+    pub rate_limit_window: i64,
+    pub rate_limit_per_ip: i64,
+    pub rate_limit_per_username: i64,
+    pub lockout_threshold: i64,
+    pub lockout_seconds: i64,
+}
+
+pub struct ValidationConfig {
+    pub username_min_length: usize,
+    pub username_max_length: usize,
+    pub display_name_min_length: usize,
+    pub display_name_max_length: usize,
+    pub question_max_length: usize,
+    pub option_max_length: usize,
+    pub poll_min_options: usize,
+    pub poll_max_options: usize,
+    pub organization_name_max_length: usize,
+    pub token_name_max_length: usize,
+    pub token_max_lifetime_days: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitBackend {
+    Memory,
+    MongoDb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateBudget {
+    pub capacity: u32,
+    pub period_seconds: u32,
+}
+
+impl RateBudget {
+    fn parse(value: &str) -> Option<RateBudget> {
+        let (capacity, period) = value.trim().split_once('/')?;
+        let capacity = capacity.trim().parse::<u32>().ok().filter(|n| *n > 0)?;
+        let period_seconds = period.trim().trim_end_matches('s').parse::<u32>().ok().filter(|n| *n > 0)?;
+        Some(RateBudget { capacity, period_seconds })
+    }
+}
+
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub backend: RateLimitBackend,
+    pub route_budgets: HashMap<String, RateBudget>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Json,
+    Pretty,
+}
+
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    pub filter: String,
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+pub struct Config {
+    pub profile: Profile,
+    pub source: Option<String>,
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub webauthn: WebauthnConfig,
+    pub jwt: JwtConfig,
+    pub session: SessionConfig,
+    pub auth_guard: AuthGuardConfig,
+    pub admin_usernames: Vec<String>,
+    pub security_event_retention_days: i64,
+    pub validation: ValidationConfig,
+    pub rate_limit: RateLimitConfig,
+    pub logging: LoggingConfig,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct ConfigErrors(pub Vec<String>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} configuration error(s):", self.0.len())?;
+        for error in &self.0 {
+            writeln!(f, "  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+struct Source {
+    file: Option<toml::Table>,
+    profile: Profile,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Source {
+    fn file_value(&self, path: &str) -> Option<&toml::Value> {
+        let (section, key) = path.split_once('.')?;
+        let file = self.file.as_ref()?;
+
+        file.get("profiles")
+            .and_then(|profiles| profiles.get(self.profile.as_str()))
+            .and_then(|profile| profile.get(section))
+            .and_then(|section| section.get(key))
+            .or_else(|| file.get(section)?.get(key))
+    }
+
+    fn raw(&self, name: &str, path: &str) -> Option<String> {
+        if let Ok(value) = env::var(name) {
+            return Some(value);
+        }
+
+        let value = self.file_value(path)?;
+
+        Some(match value {
+            toml::Value::String(value) => value.clone(),
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string).unwrap_or_else(|| item.to_string()))
+                .collect::<Vec<_>>()
+                .join(","),
+            other => other.to_string(),
+        })
+    }
+
+    fn optional(&self, name: &str, path: &str) -> Option<String> {
+        self.raw(name, path)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn string(&self, name: &str, path: &str, default: &str) -> String {
+        self.optional(name, path).unwrap_or_else(|| default.to_string())
+    }
+
+    fn required(&mut self, name: &str, path: &str) -> String {
+        self.optional(name, path).unwrap_or_else(|| {
+            self.errors.push(format!("{} (or `{}` in the config file) must be set", name, path));
+            String::new()
+        })
+    }
+
+    fn development_default(&mut self, name: &str, path: &str, default: &str) -> String {
+        if self.profile == Profile::Development {
+            self.string(name, path, default)
+        } else {
+            self.required(name, path)
+        }
+    }
+
+    fn origins(&mut self, name: &str, path: &str) -> Vec<OriginPattern> {
+        let mut patterns = Vec::new();
+        for value in self.list(name, path) {
+            match OriginPattern::parse(&value) {
+                Ok(pattern) if self.profile != Profile::Development && pattern.scheme() != "https" && !pattern.is_local() => {
+                    self.errors.push(format!("{} entry '{}' must use https in the {} profile", name, value, self.profile.as_str()));
+                }
+                Ok(pattern) if self.profile == Profile::Production && matches!(pattern, OriginPattern::Subdomains { .. }) => {
+                    self.errors.push(format!("{} entry '{}': wildcard origins are not allowed in the production profile", name, value));
+                }
+                Ok(pattern) => {
+                    if !patterns.contains(&pattern) {
+                        patterns.push(pattern);
+                    }
+                }
+                Err(e) => self.errors.push(format!("{}: {}", name, e)),
+            }
+        }
+        patterns
+    }
+
+    fn number(&mut self, name: &str, path: &str, default: i64) -> i64 {
+        let Some(value) = self.optional(name, path) else {
+            return default;
+        };
+
+        match value.parse::<i64>() {
+            Ok(number) if number > 0 => number,
+            _ => {
+                self.errors.push(format!("{} must be a positive integer, got '{}'", name, value));
+                default
+            }
+        }
+    }
+
+    fn length(&mut self, name: &str, path: &str, default: usize) -> usize {
+        self.number(name, path, default as i64) as usize
+    }
+
+    fn length_range(&mut self, min: (&str, &str, usize), max: (&str, &str, usize)) -> (usize, usize) {
+        let min_value = self.length(min.0, min.1, min.2);
+        let max_value = self.length(max.0, max.1, max.2);
+        if min_value > max_value {
+            self.errors.push(format!("{} ({}) must not be greater than {} ({})", min.0, min_value, max.0, max_value));
+        }
+        (min_value, max_value)
+    }
+
+    fn budget(&mut self, name: &str, value: &str) -> RateBudget {
+        RateBudget::parse(value).unwrap_or_else(|| {
+            self.errors.push(format!("{} must look like <requests>/<seconds>, got '{}'", name, value));
+            RateBudget { capacity: 1, period_seconds: 1 }
+        })
+    }
+
+    fn flag(&mut self, name: &str, path: &str, default: bool) -> bool {
+        let Some(value) = self.optional(name, path) else {
+            return default;
+        };
+
+        match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" => true,
+            "0" | "false" | "no" => false,
+            _ => {
+                self.errors.push(format!("{} must be true or false, got '{}'", name, value));
+                default
+            }
+        }
+    }
+
+    fn list(&self, name: &str, path: &str) -> Vec<String> {
+        self.raw(name, path)
+            .unwrap_or_default()
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    fn url(&mut self, name: &str, value: &str) -> Url {
+        Url::parse(value).unwrap_or_else(|e| {
+            if !value.is_empty() {
+                self.errors.push(format!("{} is not a valid URL ('{}'): {}", name, value, e));
+            }
+            Url::parse("http://localhost").expect("static URL is valid")
+        })
+    }
+}
+
+fn config_file_path() -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(path.to_string());
+        }
+    }
+
+    env::var("CONFIG_FILE").ok().filter(|path| !path.trim().is_empty())
+}
+
+fn load_file(path: &str) -> Result<toml::Table, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file '{}': {}", path, e))?;
+
+    raw.parse::<toml::Table>()
+        .map_err(|e| format!("Invalid config file '{}': {}", path, e))
+}
+
+impl Config {
+    pub fn load() -> Result<Config, ConfigErrors> {
+        let path = config_file_path();
+        let default_profile = if cfg!(debug_assertions) { Profile::Development } else { Profile::Production };
+        let mut source = Source { file: None, profile: default_profile, errors: Vec::new(), warnings: Vec::new() };
+
+        if let Some(path) = &path {
+            match load_file(path) {
+                Ok(table) => source.file = Some(table),
+                Err(e) => source.errors.push(e),
+            }
+        }
+
+        if let Some(profile) = source.optional("APP_PROFILE", "server.profile") {
+            match Profile::parse(&profile) {
+                Some(profile) => source.profile = profile,
+                None => source.errors.push(format!(
+                    "APP_PROFILE must be development, staging or production, got '{}'",
+                    profile
+                )),
+            }
+        }
+
+        let server_addr = source.string("SERVER_ADDR", "server.addr", "0.0.0.0:8000");
+        let addr = server_addr.parse::<SocketAddr>().unwrap_or_else(|_| {
+            source.errors.push(format!("SERVER_ADDR is not a valid socket address: '{}'", server_addr));
+            SocketAddr::from(([0, 0, 0, 0], 8000))
+        });
+
+        let cors_origins = if source.optional("CORS_ORIGINS", "server.cors_origins").is_some() {
+            source.origins("CORS_ORIGINS", "server.cors_origins")
+        } else if source.optional("CORS_ORIGIN", "server.cors_origin").is_some() {
+            source.origins("CORS_ORIGIN", "server.cors_origin")
+        } else if source.profile == Profile::Development {
+            vec![OriginPattern::Exact("http://localhost:3000".to_string())]
+        } else {
+            source.errors.push("CORS_ORIGINS (or `server.cors_origins` in the config file) must be set".to_string());
+            Vec::new()
+        };
+
+        let mut cors_allowed_methods = Vec::new();
+        for method in source.string("CORS_ALLOWED_METHODS", "server.cors_allowed_methods", DEFAULT_CORS_METHODS).split(',') {
+            match Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes()) {
+                Ok(method) => cors_allowed_methods.push(method),
+                Err(_) => source.errors.push(format!("CORS_ALLOWED_METHODS contains an invalid method '{}'", method)),
+            }
+        }
+
+        let mut cors_allowed_headers = Vec::new();
+        for header in source.string("CORS_ALLOWED_HEADERS", "server.cors_allowed_headers", DEFAULT_CORS_HEADERS).split(',') {
+            match HeaderName::from_bytes(header.trim().to_ascii_lowercase().as_bytes()) {
+                Ok(header) => cors_allowed_headers.push(header),
+                Err(_) => source.errors.push(format!("CORS_ALLOWED_HEADERS contains an invalid header '{}'", header)),
+            }
+        }
+
+        let trusted_proxy_hops = if source.flag("TRUST_PROXY_HEADERS", "server.trust_proxy_headers", false) {
+            source.length("TRUSTED_PROXY_HOPS", "server.trusted_proxy_hops", 1)
+        } else {
+            0
+        };
+
+        let metrics_token = if source.profile == Profile::Development {
+            source.optional("METRICS_TOKEN", "server.metrics_token")
+        } else {
+            Some(source.required("METRICS_TOKEN", "server.metrics_token"))
+        };
+
+        let server = ServerConfig {
+            addr,
+            cors_origins,
+            cors_allowed_methods,
+            cors_allowed_headers,
+            csrf_trusted_origins: source.origins("CSRF_TRUSTED_ORIGINS", "server.csrf_trusted_origins"),
+            trusted_proxy_hops,
+            shutdown_timeout: source.number("SHUTDOWN_TIMEOUT_SECONDS", "server.shutdown_timeout", 30) as u64,
+            metrics_token,
+        };
+
+        let database = DatabaseConfig {
+            mongo_uri: source.required("MONGO_URI", "database.mongo_uri"),
+            db_name: source.required("DB_NAME", "database.db_name"),
+        };
+
+        let rp_id = source.development_default("RP_ID", "webauthn.rp_id", "localhost");
+        let default_rp_origin = server.cors_origins.iter().find_map(|pattern| match pattern {
+            OriginPattern::Exact(origin) => Some(origin.clone()),
+            OriginPattern::Subdomains { .. } => None,
+        });
+        let rp_origin = match (source.optional("RP_ORIGIN", "webauthn.rp_origin"), default_rp_origin) {
+            (Some(rp_origin), _) | (None, Some(rp_origin)) => rp_origin,
+            (None, None) => source.development_default("RP_ORIGIN", "webauthn.rp_origin", "http://localhost:3000"),
+        };
+        let rp_origin = source.url("RP_ORIGIN", &rp_origin);
+
+        if !rp_id.is_empty() && !server.cors_origins.iter().any(|pattern| pattern.matches(rp_origin.as_str())) {
+            source.warnings.push(format!("RP_ORIGIN {} is not one of the configured CORS origins", rp_origin));
+        }
+
+        let in_rp_domain = |url: &Url| {
+            url.domain().is_some_and(|domain| domain == rp_id || domain.ends_with(&format!(".{}", rp_id)))
+        };
+        let allow_subdomains = source.flag("WEBAUTHN_ALLOW_SUBDOMAINS", "webauthn.allow_subdomains", false);
+        let mut extra_origins = Vec::new();
+        for pattern in &server.cors_origins {
+            let Some(url) = pattern.base_url().filter(|url| in_rp_domain(url)) else {
+                continue;
+            };
+            if matches!(pattern, OriginPattern::Subdomains { .. }) && !allow_subdomains {
+                source.warnings.push(format!(
+                    "CORS origin {} allows subdomains, but passkeys are only accepted from them when WEBAUTHN_ALLOW_SUBDOMAINS is set",
+                    pattern
+                ));
+                continue;
+            }
+            if url.origin() != rp_origin.origin() && !extra_origins.contains(&url) {
+                extra_origins.push(url);
+            }
+        }
+
+        let block_cloned_credentials = match source.string("WEBAUTHN_CLONE_POLICY", "webauthn.clone_policy", "block").as_str() {
+            "block" => true,
+            "warn" => false,
+            other => {
+                source.errors.push(format!("WEBAUTHN_CLONE_POLICY must be 'block' or 'warn', got '{}'", other));
+                true
+            }
+        };
+
+        let aaguid_allow_list = source.list("WEBAUTHN_AAGUID_ALLOWLIST", "webauthn.aaguid_allowlist");
+        let metadata_path = source.optional("WEBAUTHN_METADATA_PATH", "webauthn.metadata_path");
+        if !aaguid_allow_list.is_empty() && metadata_path.is_none() {
+            source.errors.push("WEBAUTHN_METADATA_PATH must be set when an AAGUID allow-list is configured".to_string());
+        }
+
+        let webauthn = WebauthnConfig {
+            rp_id,
+            rp_origin,
+            extra_origins,
+            allow_subdomains,
+            allow_any_port: source.profile == Profile::Development,
+            rp_name: source.string("RP_NAME", "webauthn.rp_name", "Polling App"),
+            require_user_verification: source.flag("WEBAUTHN_REQUIRE_USER_VERIFICATION", "webauthn.require_user_verification", true),
+            block_cloned_credentials,
+            aaguid_allow_list,
+            metadata_path,
+        };
+
+        let jwt = JwtConfig {
+            keys: source.list("JWT_KEYS", "jwt.keys"),
+            secret: source.optional("JWT_SECRET", "jwt.secret"),
+            active_kid: source.optional("JWT_ACTIVE_KID", "jwt.active_kid"),
+        };
+        if jwt.keys.is_empty() && jwt.secret.is_none() {
+            source.errors.push("Either JWT_KEYS or JWT_SECRET must be set".to_string());
+        }
+
+        let session = SessionConfig {
+            access_token_max_age: source.number("SESSION_MAX_AGE", "session.access_token_max_age", 900),
+            refresh_token_max_age: source.number("REFRESH_TOKEN_MAX_AGE", "session.refresh_token_max_age", 2592000),
+            step_up_max_age: source.number("STEP_UP_MAX_AGE", "session.step_up_max_age", 300),
+        };
+
+        let auth_guard = AuthGuardConfig {
+            decoy_key: source.optional("AUTH_DECOY_KEY", "auth.decoy_key"),
+            rate_limit_window: source.number("AUTH_RATE_LIMIT_WINDOW", "auth.rate_limit_window", 60),
+            rate_limit_per_ip: source.number("AUTH_RATE_LIMIT_PER_IP", "auth.rate_limit_per_ip", 60),
+            rate_limit_per_username: source.number("AUTH_RATE_LIMIT_PER_USERNAME", "auth.rate_limit_per_username", 10),
+            lockout_threshold: source.number("AUTH_LOCKOUT_THRESHOLD", "auth.lockout_threshold", 5),
+            lockout_seconds: source.number("AUTH_LOCKOUT_SECONDS", "auth.lockout_seconds", 900),
+        };
+
+        let admin_usernames = source.list("ADMIN_USERNAMES", "auth.admin_usernames");
+        let security_event_retention_days = source.number("SECURITY_EVENT_RETENTION_DAYS", "auth.security_event_retention_days", 180);
+
+        let (username_min_length, username_max_length) = source.length_range(
+            ("USERNAME_MIN_LENGTH", "validation.username_min_length", 3),
+            ("USERNAME_MAX_LENGTH", "validation.username_max_length", 32),
+        );
+        let (display_name_min_length, display_name_max_length) = source.length_range(
+            ("DISPLAY_NAME_MIN_LENGTH", "validation.display_name_min_length", 2),
+            ("DISPLAY_NAME_MAX_LENGTH", "validation.display_name_max_length", 64),
+        );
+        let (poll_min_options, poll_max_options) = source.length_range(
+            ("POLL_MIN_OPTIONS", "validation.poll_min_options", 2),
+            ("POLL_MAX_OPTIONS", "validation.poll_max_options", 10),
+        );
+        if poll_min_options < 2 {
+            source.errors.push(format!("POLL_MIN_OPTIONS must be at least 2, got {}", poll_min_options));
+        }
+
+        let validation = ValidationConfig {
+            username_min_length,
+            username_max_length,
+            display_name_min_length,
+            display_name_max_length,
+            question_max_length: source.length("POLL_QUESTION_MAX_LENGTH", "validation.question_max_length", 300),
+            option_max_length: source.length("POLL_OPTION_MAX_LENGTH", "validation.option_max_length", 120),
+            poll_min_options,
+            poll_max_options,
+            organization_name_max_length: source.length("ORGANIZATION_NAME_MAX_LENGTH", "validation.organization_name_max_length", 100),
+            token_name_max_length: source.length("API_TOKEN_NAME_MAX_LENGTH", "validation.token_name_max_length", 64),
+            token_max_lifetime_days: source.number("API_TOKEN_MAX_LIFETIME_DAYS", "validation.token_max_lifetime_days", 365),
+        };
+
+        let backend = match source.string("RATE_LIMIT_STORE", "rate_limit.store", "memory").to_ascii_lowercase().as_str() {
+            "memory" => RateLimitBackend::Memory,
+            "mongodb" | "mongo" => RateLimitBackend::MongoDb,
+            other => {
+                source.errors.push(format!("RATE_LIMIT_STORE must be 'memory' or 'mongodb', got '{}'", other));
+                RateLimitBackend::Memory
+            }
+        };
+
+        let mut route_budgets = HashMap::new();
+        for (route, budget) in DEFAULT_ROUTE_BUDGETS {
+            route_budgets.insert(route.to_string(), source.budget(&format!("Built-in rate limit for {}", route), budget));
+        }
+        for entry in source.list("RATE_LIMIT_ROUTES", "rate_limit.routes") {
+            match entry.split_once('=') {
+                Some((route, budget)) => {
+                    let route = route.trim();
+                    if !route_budgets.contains_key(route) {
+                        let known = DEFAULT_ROUTE_BUDGETS.iter().map(|(route, _)| *route).collect::<Vec<_>>().join(", ");
+                        source.errors.push(format!("RATE_LIMIT_ROUTES names unknown route '{}', expected one of {}", route, known));
+                        continue;
+                    }
+                    let budget = source.budget("RATE_LIMIT_ROUTES", budget);
+                    route_budgets.insert(route.to_string(), budget);
+                }
+                None => source.errors.push(format!("RATE_LIMIT_ROUTES entry '{}' must look like <route>=<requests>/<seconds>", entry)),
+            }
+        }
+
+        let rate_limit = RateLimitConfig {
+            enabled: source.flag("RATE_LIMIT_ENABLED", "rate_limit.enabled", true),
+            backend,
+            route_budgets,
+        };
+
+        let format = match source.string("LOG_FORMAT", "logging.format", "json").to_ascii_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            "pretty" | "text" => LogFormat::Pretty,
+            other => {
+                source.errors.push(format!("LOG_FORMAT must be 'json' or 'pretty', got '{}'", other));
+                LogFormat::Json
+            }
+        };
+
+        let logging = LoggingConfig {
+            format,
+            filter: source.string("RUST_LOG", "logging.filter", "info"),
+            otlp_endpoint: source.optional("OTEL_EXPORTER_OTLP_ENDPOINT", "logging.otlp_endpoint"),
+            service_name: source.string("OTEL_SERVICE_NAME", "logging.service_name", "polling-backend"),
+        };
+        if let Some(endpoint) = &logging.otlp_endpoint {
+            source.url("OTEL_EXPORTER_OTLP_ENDPOINT", endpoint);
+        }
+
+        if !source.errors.is_empty() {
+            return Err(ConfigErrors(source.errors));
+        }
+
+        Ok(Config {
+            profile: source.profile,
+            source: path,
+            server,
+            database,
+            webauthn,
+            jwt,
+            session,
+            auth_guard,
+            admin_usernames,
+            security_event_retention_days,
+            validation,
+            rate_limit,
+            logging,
+            warnings: source.warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rate_budgets() {
+        assert_eq!(RateBudget::parse("10/60"), Some(RateBudget { capacity: 10, period_seconds: 60 }));
+        assert_eq!(RateBudget::parse(" 5 / 30s "), Some(RateBudget { capacity: 5, period_seconds: 30 }));
+    }
+
+    #[test]
+    fn rejects_malformed_rate_budgets() {
+        for value in ["", "10", "10/", "/60", "0/60", "10/0", "-1/60", "ten/60", "10/60m"] {
+            assert_eq!(RateBudget::parse(value), None, "{}", value);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::user_models::Role;
+use crate::utils::validation::{Validate, Validator};
+
+#[derive(Serialize, ToSchema)]
+pub struct UserSummary {
+    pub id: String,
+    pub username: String,
+    pub display_name: String,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQuery {
+    /// Page size, at most 200
+    pub limit: Option<i64>,
+    /// Return users whose username sorts after this one (the last username of the previous page)
+    pub after: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateRoleRequest {
+    pub role: Role,
+}
+
+impl Validate for UpdateRoleRequest {
+    fn validate(&mut self, _v: &mut Validator) {}
+}
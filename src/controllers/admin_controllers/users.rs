@@ -0,0 +1,244 @@
+use axum::{Json, extract::{Path, Query, State}};
+use futures::stream::TryStreamExt;
+use mongodb::bson::{Document, doc, oid::ObjectId};
+use serde_json::json;
+
+use crate::{
+    controllers::admin_controllers::models::{ListUsersQuery, UpdateRoleRequest, UserSummary},
+    middleware::{auth_user::AuthUser, client_context::ClientContext},
+    models::{organization_models::Membership, poll_models::Poll, user_models::User},
+    utils::{
+        api_tokens, passkeys, refresh_token, step_up,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+fn summarize(user: User) -> UserSummary {
+    UserSummary {
+        id: user.id.to_hex(),
+        username: user.username,
+        display_name: user.display_name,
+        role: user.role,
+        created_at: user.created_at,
+    }
+}
+
+async fn find_user(state: &AppState, user_id: &str) -> AppResult<User> {
+    let user_obj_id = ObjectId::parse_str(user_id)
+        .map_err(|_| AppError::invalid("user_id", ErrorCode::InvalidId, "Invalid user id"))?;
+
+    state.db.collection::<User>("users")
+        .find_one(doc! { "_id": user_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::UserNotFound, "User not found".to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/users",
+    tag = "admin",
+    summary = "List users",
+    params(ListUsersQuery),
+    responses(
+        (status = 200, description = "Users", body = Vec<UserSummary>),
+        (status = 400, description = "Invalid page size", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    Query(query): Query<ListUsersQuery>,
+) -> AppResult<Json<Vec<UserSummary>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(AppError::invalid("limit", ErrorCode::OutOfRange, format!("Must be between 1 and {}", MAX_PAGE_SIZE)));
+    }
+
+    let filter = match &query.after {
+        Some(after) => doc! { "username": { "$gt": after } },
+        None => doc! {},
+    };
+
+    let users: Vec<User> = state.db.collection::<User>("users")
+        .find(filter)
+        .sort(doc! { "username": 1 })
+        .limit(limit)
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(Json(users.into_iter().map(summarize).collect()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/users/{userId}/role",
+    tag = "admin",
+    summary = "Change a user's role",
+    request_body = UpdateRoleRequest,
+    params(
+        ("userId" = String, Path, description = "User id"),
+    ),
+    responses(
+        (status = 200, description = "Updated user", body = UserSummary),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "The user has no passkey that meets the policy for the new role", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn update_user_role(
+    Path(user_id): Path<String>,
+    State(state): State<AppState>,
+    admin: AuthUser,
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<UpdateRoleRequest>,
+) -> AppResult<Json<UserSummary>> {
+    step_up::require_recent_step_up(&state, &admin).await?;
+
+    let mut user = find_user(&state, &user_id).await?;
+
+    if user.id == admin.id && body.role != user.role {
+        return Err(AppError::Conflict(ErrorCode::SelfModification, "You cannot change your own role".to_string()));
+    }
+
+    if user.role != body.role
+        && !passkeys::has_passkey_for_role(&state.db, &state.authenticator_policy, user.id, body.role).await?
+    {
+        return Err(AppError::Conflict(
+            ErrorCode::AdminAuthenticatorRequired,
+            "The user must register an attested hardware passkey before becoming an admin".to_string(),
+        ));
+    }
+
+    if user.role != body.role {
+        state.db.collection::<User>("users")
+            .update_one(
+                doc! { "_id": user.id },
+                doc! { "$set": { "role": body.role.as_str() } },
+            )
+            .await?;
+
+        refresh_token::revoke_user_sessions(&state.db, user.id).await?;
+
+        security_events::record_security_event(
+            &state.db,
+            NewSecurityEvent::new(user.id, &user.username, security_events::ROLE_CHANGED, security_events::SUCCESS, &client)
+                .detail(format!(
+                    "Role changed from {} to {} by {}",
+                    user.role.as_str(),
+                    body.role.as_str(),
+                    admin.username
+                )),
+        )
+        .await?;
+
+        tracing::info!(admin = %admin.username, user = %user.username, role = body.role.as_str(), "User role changed");
+        user.role = body.role;
+    }
+
+    Ok(Json(summarize(user)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{userId}",
+    tag = "admin",
+    summary = "Delete a user",
+    params(
+        ("userId" = String, Path, description = "User id"),
+    ),
+    responses(
+        (status = 200, description = "User deleted", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "The user still owns polls or is the last owner of an organization", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn delete_user(
+    Path(user_id): Path<String>,
+    State(state): State<AppState>,
+    admin: AuthUser,
+) -> AppResult<Json<serde_json::Value>> {
+    step_up::require_recent_step_up(&state, &admin).await?;
+
+    let user = find_user(&state, &user_id).await?;
+
+    if user.id == admin.id {
+        return Err(AppError::Conflict(ErrorCode::SelfModification, "You cannot delete your own account".to_string()));
+    }
+
+    let polls = state.db.collection::<Poll>("polls");
+    let owned_polls = polls.count_documents(doc! { "creator_id": user.id }).await?;
+    if owned_polls > 0 {
+        return Err(AppError::Conflict(
+            ErrorCode::UserOwnsResources,
+            format!("The user still owns {} poll(s); transfer or delete them first", owned_polls),
+        ));
+    }
+
+    let memberships = state.db.collection::<Membership>("memberships");
+    let owned: Vec<Membership> = memberships
+        .find(doc! { "user_id": user.id, "role": "owner" })
+        .await?
+        .try_collect()
+        .await?;
+    for membership in owned {
+        let owners = memberships
+            .count_documents(doc! { "organization_id": membership.organization_id, "role": "owner" })
+            .await?;
+        if owners <= 1 {
+            return Err(AppError::Conflict(
+                ErrorCode::UserOwnsResources,
+                "The user is the last owner of an organization; add another owner first".to_string(),
+            ));
+        }
+    }
+
+    refresh_token::revoke_user_sessions(&state.db, user.id).await?;
+    api_tokens::revoke_user_api_tokens(&state.db, user.id).await?;
+
+    polls
+        .update_many(
+            doc! { "collaborators.user_id": user.id },
+            doc! { "$pull": { "collaborators": { "user_id": user.id } } },
+        )
+        .await?;
+    memberships.delete_many(doc! { "user_id": user.id }).await?;
+    state.db.collection::<Document>("invitations")
+        .delete_many(doc! { "user_id": user.id, "status": "pending" })
+        .await?;
+
+    // Votes stay counted in the poll totals, so the records are kept but no longer tied to the account.
+    state.db.collection::<Document>("vote_records")
+        .update_many(doc! { "user_id": user.id }, doc! { "$set": { "user_id": null } })
+        .await?;
+
+    state.db.collection::<Document>("passkeys")
+        .delete_many(doc! { "user_id": user.id })
+        .await?;
+
+    state.db.collection::<User>("users")
+        .delete_one(doc! { "_id": user.id })
+        .await?;
+
+    tracing::info!(admin = %admin.username, user = %user.username, "User deleted");
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "User deleted"
+    })))
+}
@@ -0,0 +1,132 @@
+use axum::{Json, extract::{Path, State}};
+use mongodb::bson::oid::ObjectId;
+use serde_json::json;
+
+use crate::{
+    controllers::auth_controllers::models::{ApiTokenSummary, CreateApiTokenRequest, CreatedApiTokenResponse},
+    middleware::{auth_user::AuthUser, client_context::ClientContext},
+    models::api_token_models::ApiToken,
+    utils::{
+        api_tokens, step_up,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+fn summarize(token: ApiToken) -> ApiTokenSummary {
+    ApiTokenSummary {
+        id: token.id.to_hex(),
+        name: token.name,
+        prefix: token.prefix,
+        scopes: token.scopes,
+        created_at: token.created_at,
+        expires_at: token.expires_at,
+        last_used_at: token.last_used_at,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/api-tokens",
+    tag = "auth",
+    summary = "List personal API tokens",
+    responses(
+        (status = 200, description = "Active API tokens", body = Vec<ApiTokenSummary>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_api_tokens(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<ApiTokenSummary>>> {
+    let tokens = api_tokens::api_tokens_for_user(&state.db, user.id).await?;
+
+    Ok(Json(tokens.into_iter().map(summarize).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/api-tokens",
+    tag = "auth",
+    summary = "Create a personal API token (requires step-up)",
+    request_body = CreateApiTokenRequest,
+    responses(
+        (status = 200, description = "Created token, the secret is only returned once", body = CreatedApiTokenResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn create_api_token(
+    State(state): State<AppState>,
+    user: AuthUser,
+    client: ClientContext,
+    ValidatedJson(payload): ValidatedJson<CreateApiTokenRequest>,
+) -> AppResult<Json<CreatedApiTokenResponse>> {
+    step_up::require_recent_step_up(&state, &user).await?;
+
+    let mut scopes = Vec::new();
+    for scope in payload.scopes {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+
+    let (token, secret) = api_tokens::create_api_token(&state.db, user.id, &payload.name, scopes, payload.expires_in_days).await?;
+
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user.id, &user.username, security_events::API_TOKEN_CREATED, security_events::SUCCESS, &client)
+            .detail(format!("Created API token '{}' ({})", token.name, token.prefix)),
+    )
+    .await?;
+
+    Ok(Json(CreatedApiTokenResponse {
+        api_token: summarize(token),
+        token: secret,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/api-tokens/{tokenId}",
+    tag = "auth",
+    summary = "Revoke a personal API token",
+    params(
+        ("tokenId" = String, Path, description = "API token id"),
+    ),
+    responses(
+        (status = 200, description = "Token revoked", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn revoke_api_token(
+    Path(token_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+    client: ClientContext,
+) -> AppResult<Json<serde_json::Value>> {
+    let token_obj_id = ObjectId::parse_str(&token_id)
+        .map_err(|_| AppError::invalid("token_id", ErrorCode::InvalidId, "Invalid API token id"))?;
+
+    let token = api_tokens::revoke_api_token(&state.db, user.id, token_obj_id).await?;
+
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user.id, &user.username, security_events::API_TOKEN_REVOKED, security_events::SUCCESS, &client)
+            .detail(format!("Revoked API token '{}' ({})", token.name, token.prefix)),
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "API token revoked"
+    })))
+}
@@ -1,26 +1,46 @@
-use axum::{Json, extract::State, http::{HeaderValue, header::SET_COOKIE}};
+use axum::{Json, extract::State};
 use axum::response::IntoResponse;
 use mongodb::bson::{Document, doc};
 use webauthn_rs::prelude::*;
 use crate::{
-    controllers::auth_controllers::models::{AuthFinishRequest, AuthResponse}, 
-    utils::{session, error::{AppError, AppResult}},
+    controllers::auth_controllers::models::{AuthFinishRequest, AuthResponse},
+    middleware::client_context::ClientContext,
+    utils::{
+        passkeys, refresh_token, session,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
     state::AppState,
 };
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/login/finish",
+    tag = "auth",
+    summary = "Finish passkey sign-in",
+    request_body = AuthFinishRequest,
+    responses(
+        (status = 200, description = "Signed in, session cookies set", body = AuthResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
 pub async fn auth_finish(
     State(state): State<AppState>,
-    Json(body): Json<AuthFinishRequest>,
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<AuthFinishRequest>,
 ) -> AppResult<impl IntoResponse> {
-    if body.username.is_empty() {
-        return Err(AppError::ValidationError("Username is required".to_string()));
-    }
+    state.auth_guard.check_username(&state.db, &body.username).await?;
+    state.auth_guard.ensure_not_locked(&state.db, &body.username).await?;
 
     let auth_challenge_collection = state.db.collection::<Document>("auth_challenges");
     let challenge_doc = auth_challenge_collection
         .find_one(doc! { "username": &body.username })
         .await?
-        .ok_or_else(|| AppError::NotFound("Authentication challenge not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::ChallengeNotFound, "Authentication challenge not found".to_string()))?;
 
     let state_json = challenge_doc
         .get_str("state")
@@ -29,21 +49,18 @@ pub async fn auth_finish(
     let auth_state: PasskeyAuthentication = serde_json::from_str(state_json)?;
 
     let credential_json: PublicKeyCredential = serde_json::from_value(body.credential)
-        .map_err(|e| AppError::BadRequest(format!("Invalid credential format: {}", e)))?;
-
-    let auth_result = state.webauthn
-        .finish_passkey_authentication(&credential_json, &auth_state)
-        .map_err(|e| AppError::AuthenticationError(format!("Authentication verification failed: {}", e)))?;
-
-    let credential_id = auth_result.cred_id().to_vec();
-    let credential_id_base64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &credential_id);
-
-    let passkeys_collection = state.db.collection::<Document>("passkeys");
-    
-    let passkey_doc = passkeys_collection
-        .find_one(doc! { "credential_id": &credential_id_base64 })
-        .await?
-        .ok_or_else(|| AppError::NotFound("Passkey not found".to_string()))?;
+        .map_err(|e| AppError::BadRequest(ErrorCode::InvalidCredential, format!("Invalid credential format: {}", e)))?;
+
+    let authenticated = match passkeys::finish_authentication(&state, &client, &credential_json, &auth_state).await {
+        Ok(authenticated) => authenticated,
+        Err(e) => {
+            state.auth_guard.record_failed_verification(&state.db, &body.username).await?;
+            let credential_id = passkeys::encode_credential_id(credential_json.raw_id.as_ref());
+            security_events::record_failed_login(&state.db, &client, &body.username, &credential_id, &e.to_string()).await?;
+            return Err(e);
+        }
+    };
+    let passkey_doc = &authenticated.passkey_doc;
 
     let user_id = passkey_doc
         .get_object_id("user_id")
@@ -58,54 +75,40 @@ pub async fn auth_finish(
     let user_doc = users_collection
         .find_one(doc! { "_id": user_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::UserNotFound, "User not found".to_string()))?;
 
     let display_name = user_doc
         .get_str("display_name")
         .map_err(|e| AppError::InternalError(format!("Failed to get display_name: {}", e)))?
         .to_string();
 
-    let updated_passkey_json = serde_json::to_string(&auth_result)?;
-
-    passkeys_collection
-        .update_one(
-            doc! { "credential_id": &credential_id_base64 },
-            doc! { 
-                "$set": { 
-                    "passkey_data": updated_passkey_json,
-                    "counter": auth_result.counter() as i32,
-                    "last_used_at": chrono::Utc::now().to_rfc3339(),
-                } 
-            },
-        )
-        .await?;
-
     auth_challenge_collection
         .delete_one(doc! { "username": &body.username })
         .await?;
 
-    let token = session::create_token(&username)
-        .map_err(|e| AppError::InternalError(format!("Failed to create session token: {}", e)))?;
+    state.auth_guard.clear_failures(&state.db, &body.username).await?;
+
+    let issued = refresh_token::issue_session(&state, user_id, &username, &display_name).await?;
+
+    let credential_id = passkey_doc.get_str("credential_id").unwrap_or_default();
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user_id, &username, security_events::LOGIN, security_events::SUCCESS, &client)
+            .credential(credential_id),
+    )
+    .await?;
 
     let response = AuthResponse {
         success: true,
         username: username.clone(),
         display_name,
-        token: token.clone(),
+        token: issued.access_token.clone(),
         user_id: user_id.to_hex(),
+        security_warning: authenticated.security_warning,
     };
 
-    let cookie_value = format!(
-        "token={}; Path=/; HttpOnly; Secure; SameSite=None; Max-Age=86400",
-        token
-    );
-
     let mut resp = Json(response).into_response();
-    resp.headers_mut().insert(
-        SET_COOKIE,
-        HeaderValue::from_str(&cookie_value)
-            .map_err(|e| AppError::InternalError(format!("Failed to create cookie header: {}", e)))?
-    );
+    session::set_session_cookies(&mut resp, &state.config.session, &issued.access_token, &issued.refresh_token)?;
 
     Ok(resp)
-}
\ No newline at end of file
+}
@@ -1,73 +1,57 @@
-use axum::{Json, extract::Extension};
-use futures::stream::TryStreamExt;
-use mongodb::{
-    Database,
-    bson::{DateTime as BsonDateTime, Document, doc},
-};
-use std::sync::Arc;
-use webauthn_rs::prelude::*;
+use axum::{Json, extract::State};
+use mongodb::bson::{DateTime as BsonDateTime, Document, doc};
 use crate::{
     controllers::auth_controllers::models::AuthStartRequest,
-    utils::error::{AppError, AppResult}
+    utils::{passkeys, error::{AppError, AppResult, ErrorResponse}, validation::ValidatedJson},
+    state::AppState,
 };
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/login/start",
+    tag = "auth",
+    summary = "Start passkey sign-in",
+    request_body = AuthStartRequest,
+    responses(
+        (status = 200, description = "WebAuthn credential request options", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
 pub async fn auth_start(
-    Extension(db): Extension<Arc<Database>>,
-    Extension(webauthn): Extension<Arc<Webauthn>>,
-    Json(body): Json<AuthStartRequest>,
+    State(state): State<AppState>,
+    ValidatedJson(body): ValidatedJson<AuthStartRequest>,
 ) -> AppResult<Json<serde_json::Value>> {
-    if body.username.is_empty() {
-        return Err(AppError::ValidationError("Username is required".to_string()));
-    }
+    state.auth_guard.check_username(&state.db, &body.username).await?;
+    state.auth_guard.ensure_not_locked(&state.db, &body.username).await?;
 
-    let users = db.collection::<Document>("users");
+    let users = state.db.collection::<Document>("users");
 
-    let user_doc = users
+    let user_id = users
         .find_one(doc! { "username": &body.username })
         .await?
-        .ok_or_else(|| {
-            eprintln!("❌ User not found: {}", &body.username);
-            AppError::NotFound(format!("User '{}' not found", &body.username))
-        })?;
-
-    let user_id = user_doc
-        .get_object_id("_id")
-        .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?;
-
-    let passkeys_collection = db.collection::<Document>("passkeys");
-
-    let passkey_docs: Vec<Document> = passkeys_collection
-        .find(doc! { "user_id": user_id })
-        .await?
-        .try_collect()
-        .await?;
-
-    ("✅ Found {} passkey documents", passkey_docs.len());
-
-    if passkey_docs.is_empty() {
-        eprintln!("❌ No passkeys found for user: {}", &body.username);
-        return Err(AppError::NotFound(format!("No passkeys found for user '{}'", &body.username)));
-    }
-
-    let mut passkeys: Vec<Passkey> = Vec::new();
-
-    for (_index, doc) in passkey_docs.iter().enumerate() {        
-        let passkey_doc = doc
-            .get_document("passkey")
-            .map_err(|e| AppError::InternalError(format!("Failed to get passkey document: {}", e)))?;
-
-        let passkey: Passkey = mongodb::bson::from_document(passkey_doc.clone())?;
-
-        passkeys.push(passkey);
-    }
-
-    let (rcr, auth_state) = webauthn
-        .start_passkey_authentication(&passkeys)
+        .and_then(|user_doc| user_doc.get_object_id("_id").ok());
+
+    let user_passkeys = match user_id {
+        Some(user_id) => passkeys::authentication_passkeys(&state, user_id).await?,
+        None => Vec::new(),
+    };
+
+    // Unknown usernames and accounts without usable passkeys get a stable decoy challenge,
+    // so the response does not reveal which accounts exist.
+    let user_passkeys = if user_passkeys.is_empty() {
+        state.auth_guard.decoy_passkeys(&body.username)?
+    } else {
+        user_passkeys
+    };
+
+    let (rcr, auth_state) = state.webauthn
+        .start_passkey_authentication(&user_passkeys)
         .map_err(|e| AppError::WebauthnError(format!("Failed to start authentication: {}", e)))?;
 
     let state_json = serde_json::to_string(&auth_state)?;
 
-    let auth_challenge_collection = db.collection::<Document>("auth_challenges");
+    let auth_challenge_collection = state.db.collection::<Document>("auth_challenges");
 
     auth_challenge_collection
         .delete_many(doc! { "username": &body.username })
@@ -0,0 +1,38 @@
+use axum::{Json, extract::State};
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::cookie::CookieJar;
+
+use crate::{
+    controllers::auth_controllers::models::CsrfTokenResponse,
+    utils::{csrf, session, error::AppResult},
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/csrf",
+    tag = "auth",
+    summary = "Issue a CSRF token for the double-submit cookie",
+    responses(
+        (status = 200, description = "CSRF token, also set as a cookie", body = CsrfTokenResponse),
+    ),
+)]
+pub async fn csrf_token(
+    State(state): State<AppState>,
+    cookie_jar: CookieJar,
+) -> AppResult<Response> {
+    let csrf_token = cookie_jar
+        .get(csrf::CSRF_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(csrf::generate_csrf_token);
+
+    let mut response = Json(CsrfTokenResponse {
+        csrf_token: csrf_token.clone(),
+        header: csrf::CSRF_HEADER,
+    }).into_response();
+
+    session::set_csrf_cookie(&mut response, &state.config.session, &csrf_token)?;
+
+    Ok(response)
+}
@@ -0,0 +1,17 @@
+use axum::{Json, extract::State};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::state::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    summary = "Public keys for verifying access tokens",
+    responses(
+        (status = 200, description = "JSON Web Key Set", body = Object),
+    ),
+)]
+pub async fn jwks(State(state): State<AppState>) -> Json<JwkSet> {
+    Json(state.jwt_keys.jwks())
+}
@@ -1,41 +1,62 @@
 use axum::Json;
+use axum::extract::State;
 use axum::response::{IntoResponse, Response};
-use axum::http::header::{SET_COOKIE, HeaderValue, COOKIE};
-use axum::extract::Request;
-use crate::utils::{session, error::{AppError, AppResult}};
+use axum_extra::extract::cookie::CookieJar;
+use mongodb::bson::oid::ObjectId;
+use crate::middleware::client_context::ClientContext;
+use crate::state::AppState;
+use crate::utils::{refresh_token, session, error::AppResult};
+use crate::utils::security_events::{self, NewSecurityEvent};
 
-pub async fn logout(request: Request) -> AppResult<Response> {
-    
-    let cookies_header = request.headers().get(COOKIE);
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    summary = "Sign out and revoke the session",
+    responses(
+        (status = 200, description = "Signed out, session cookies cleared", body = Object),
+    ),
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    client: ClientContext,
+    cookie_jar: CookieJar,
+) -> AppResult<Response> {
+    let mut logged_out: Option<(ObjectId, String)> = None;
 
-    if let Some(cookies) = cookies_header {
-        if let Ok(cookie_str) = cookies.to_str() {
-            for cookie in cookie_str.split(';') {
-                let cookie = cookie.trim();
-                if let Some((name, value)) = cookie.split_once('=') {
-                    if name == "token" {
-                        let _display_len = 20.min(value.len());                        
-                        if let Ok(claims) = session::verify_token(value) {
-                            println!("=== Logout for: {} ===", claims.sub);
-                        }
-                    }
-                }
-            }
+    if let Some(token) = cookie_jar.get(session::ACCESS_COOKIE)
+        && let Ok(claims) = session::verify_token(&state.jwt_keys, token.value())
+    {
+        tracing::info!(user = %claims.username, "Logout");
+        if let Ok(session_id) = ObjectId::parse_str(&claims.sid) {
+            refresh_token::revoke_session(&state.db, session_id).await?;
         }
+        if let Ok(user_id) = ObjectId::parse_str(&claims.sub) {
+            logged_out = Some((user_id, claims.username));
+        }
+    }
+
+    if let Some(refresh) = cookie_jar.get(session::REFRESH_COOKIE)
+        && let Some(revoked) = refresh_token::revoke_session_by_refresh_token(&state.db, refresh.value()).await?
+        && logged_out.is_none()
+    {
+        logged_out = Some((revoked.user_id, revoked.username));
+    }
+
+    if let Some((user_id, username)) = logged_out {
+        security_events::record_security_event(
+            &state.db,
+            NewSecurityEvent::new(user_id, &username, security_events::LOGOUT, security_events::SUCCESS, &client),
+        )
+        .await?;
     }
 
-    let cookie_value = "token=; Path=/; HttpOnly; Secure; SameSite=None; Max-Age=0";
-    
     let mut response = Json(serde_json::json!({
         "success": true,
         "message": "Logged out successfully"
     })).into_response();
-    
-    response.headers_mut().insert(
-        SET_COOKIE,
-        HeaderValue::from_str(cookie_value)
-            .map_err(|e| AppError::InternalError(format!("Failed to create cookie header: {}", e)))?
-    );
+
+    session::clear_session_cookies(&mut response)?;
 
     Ok(response)
-}
\ No newline at end of file
+}
@@ -0,0 +1,26 @@
+use axum::Json;
+
+use crate::controllers::auth_controllers::models::MeResponse;
+use crate::middleware::auth_user::AuthUser;
+use crate::utils::error::ErrorResponse;
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    summary = "Current user",
+    responses(
+        (status = 200, description = "Signed-in user", body = MeResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn me(user: AuthUser) -> Json<MeResponse> {
+    Json(MeResponse {
+        user_id: user.id.to_hex(),
+        username: user.username,
+        display_name: user.display_name,
+        session_id: user.session_id.to_hex(),
+        role: user.role,
+    })
+}
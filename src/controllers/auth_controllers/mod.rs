@@ -3,4 +3,17 @@ pub mod auth_start;
 pub mod register_finish;
 pub mod register_start;
 pub mod models;
-pub mod logout;
\ No newline at end of file
+pub mod logout;
+pub mod refresh;
+pub mod jwks;
+pub mod me;
+pub mod recover_start;
+pub mod recover_finish;
+pub mod regenerate_recovery_codes;
+pub mod step_up_start;
+pub mod step_up_finish;
+pub mod passkeys;
+pub mod security_events;
+pub mod api_tokens;
+pub mod csrf_token;
+
@@ -1,5 +1,9 @@
+use chrono::{DateTime, Utc};
 use mongodb::bson::oid::ObjectId;
+use crate::models::{api_token_models::ApiScope, user_models::Role};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::utils::validation::{Validate, Validator};
 
 fn serialize_object_id_as_string<S>(oid: &ObjectId, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -8,44 +12,206 @@ where
     serializer.serialize_str(&oid.to_hex())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterStartRequest {
     pub username: String,
     pub display_name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterFinishRequest {
     pub username: String,
     pub credential: serde_json::Value,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AuthStartRequest {
     pub username: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AuthFinishRequest {
     pub username: String,
     pub credential: serde_json::Value,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, ToSchema)]
+pub struct RecoverStartRequest {
+    pub username: String,
+    pub recovery_code: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RecoverFinishRequest {
+    pub username: String,
+    pub credential: serde_json::Value,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RecoverStartResponse {
+    pub options: serde_json::Value,
+    pub remaining_recovery_codes: usize,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct AuthResponse {
     pub success: bool,
     pub username: String,
     pub display_name: String,
     pub token: String,
     pub user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_warning: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct RegisterResponse {
     pub success: bool,
     pub username: String,
     pub display_name: String,
     pub token: String,
     #[serde(serialize_with = "serialize_object_id_as_string")]
+    #[schema(value_type = String)]
     pub user_id: ObjectId, 
-}
\ No newline at end of file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recovery_codes: Vec<String>,
+}
+#[derive(Serialize, ToSchema)]
+pub struct RefreshResponse {
+    pub success: bool,
+    pub username: String,
+    pub user_id: String,
+    pub token: String,
+    pub expires_in: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MeResponse {
+    pub user_id: String,
+    pub username: String,
+    pub display_name: String,
+    pub session_id: String,
+    pub role: Role,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct StepUpFinishRequest {
+    pub credential: serde_json::Value,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StepUpResponse {
+    pub success: bool,
+    pub expires_in: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PasskeySummary {
+    pub id: String,
+    pub credential_id: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub clone_suspected: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SecurityEventSummary {
+    pub id: String,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub credential_id: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiTokenSummary {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreatedApiTokenResponse {
+    #[serde(flatten)]
+    pub api_token: ApiTokenSummary,
+    pub token: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CsrfTokenResponse {
+    pub csrf_token: String,
+    pub header: &'static str,
+}
+
+impl Validate for RegisterStartRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        let limits = v.limits();
+        v.text("username", &mut self.username, limits.username_min_length, limits.username_max_length);
+        v.text("display_name", &mut self.display_name, limits.display_name_min_length, limits.display_name_max_length);
+    }
+}
+
+impl Validate for RegisterFinishRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.required("username", &mut self.username);
+    }
+}
+
+impl Validate for AuthStartRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+    }
+}
+
+impl Validate for AuthFinishRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+    }
+}
+
+impl Validate for RecoverStartRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+        v.required("recovery_code", &mut self.recovery_code);
+    }
+}
+
+impl Validate for RecoverFinishRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+    }
+}
+
+impl Validate for CreateApiTokenRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        let limits = v.limits();
+        v.text("name", &mut self.name, 1, limits.token_name_max_length);
+        v.count("scopes", self.scopes.len(), 1, usize::MAX);
+        if let Some(days) = self.expires_in_days {
+            v.range("expires_in_days", days, 1, limits.token_max_lifetime_days);
+        }
+    }
+}
+
+impl Validate for StepUpFinishRequest {
+    fn validate(&mut self, _v: &mut Validator) {}
+}
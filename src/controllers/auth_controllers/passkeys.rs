@@ -0,0 +1,113 @@
+use axum::{Json, extract::{Path, State}};
+use futures::stream::TryStreamExt;
+use mongodb::bson::{Document, doc, oid::ObjectId};
+use serde_json::json;
+
+use crate::{
+    controllers::auth_controllers::models::PasskeySummary,
+    middleware::{auth_user::AuthUser, client_context::ClientContext},
+    utils::{
+        step_up,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/passkeys",
+    tag = "auth",
+    summary = "List passkeys",
+    responses(
+        (status = 200, description = "Registered passkeys", body = Vec<PasskeySummary>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_passkeys(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<PasskeySummary>>> {
+    let passkey_docs: Vec<Document> = state.db.collection::<Document>("passkeys")
+        .find(doc! { "user_id": user.id })
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut summaries = Vec::new();
+    for passkey_doc in passkey_docs {
+        summaries.push(PasskeySummary {
+            id: passkey_doc
+                .get_object_id("_id")
+                .map_err(|e| AppError::InternalError(format!("Failed to get passkey id: {}", e)))?
+                .to_hex(),
+            credential_id: passkey_doc
+                .get_str("credential_id")
+                .map_err(|e| AppError::InternalError(format!("Failed to get credential_id: {}", e)))?
+                .to_string(),
+            created_at: passkey_doc.get_datetime("created_at").ok().map(|dt| dt.to_chrono()),
+            last_used_at: passkey_doc.get_datetime("last_used_at").ok().map(|dt| dt.to_chrono()),
+            clone_suspected: passkey_doc.get_bool("clone_suspected").unwrap_or(false),
+        });
+    }
+
+    Ok(Json(summaries))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/passkeys/{passkeyId}",
+    tag = "auth",
+    summary = "Revoke a passkey (requires step-up)",
+    params(
+        ("passkeyId" = String, Path, description = "Passkey id"),
+    ),
+    responses(
+        (status = 200, description = "Passkey revoked", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn revoke_passkey(
+    Path(passkey_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+    client: ClientContext,
+) -> AppResult<Json<serde_json::Value>> {
+    let passkey_obj_id = ObjectId::parse_str(&passkey_id)
+        .map_err(|_| AppError::invalid("passkey_id", ErrorCode::InvalidId, "Invalid passkey id"))?;
+
+    step_up::require_recent_step_up(&state, &user).await?;
+
+    let passkeys = state.db.collection::<Document>("passkeys");
+
+    let remaining = passkeys
+        .count_documents(doc! { "user_id": user.id })
+        .await?;
+
+    if remaining <= 1 {
+        return Err(AppError::Conflict(ErrorCode::LastPasskey, "You cannot remove your only passkey".to_string()));
+    }
+
+    let removed = passkeys
+        .find_one_and_delete(doc! { "_id": passkey_obj_id, "user_id": user.id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PasskeyNotFound, "Passkey not found".to_string()))?;
+
+    let credential_id = removed.get_str("credential_id").unwrap_or_default();
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user.id, &user.username, security_events::PASSKEY_REMOVED, security_events::SUCCESS, &client)
+            .credential(credential_id),
+    )
+    .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Passkey removed"
+    })))
+}
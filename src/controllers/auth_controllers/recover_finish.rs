@@ -0,0 +1,95 @@
+use axum::{Json, extract::State};
+use axum::response::{IntoResponse, Response};
+use mongodb::bson::{Document, doc};
+use webauthn_rs::prelude::*;
+
+use crate::{
+    controllers::auth_controllers::models::{AuthResponse, RecoverFinishRequest},
+    middleware::client_context::ClientContext,
+    models::user_models::User,
+    utils::{
+        authenticator_policy::{self, PendingRegistration},
+        challenges, passkeys, refresh_token, session,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/recover/finish",
+    tag = "auth",
+    summary = "Finish account recovery",
+    request_body = RecoverFinishRequest,
+    responses(
+        (status = 200, description = "Recovered and signed in, session cookies set", body = AuthResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
+pub async fn recover_finish(
+    State(state): State<AppState>,
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<RecoverFinishRequest>,
+) -> AppResult<Response> {
+    let recovery_challenges = state.db.collection::<Document>("recovery_challenges");
+    let challenge_doc = recovery_challenges
+        .find_one(doc! { "username": &body.username, "created_at": { "$gt": challenges::issued_after() } })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::ChallengeNotFound, "Recovery challenge not found or expired".to_string()))?;
+
+    let state_json = challenge_doc
+        .get_str("state")
+        .map_err(|e| AppError::InternalError(format!("Failed to get state: {}", e)))?;
+
+    let user_id = challenge_doc
+        .get_object_id("user_id")
+        .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?;
+
+    let reg_state: PendingRegistration = serde_json::from_str(state_json)?;
+
+    let credential: RegisterPublicKeyCredential = serde_json::from_value(body.credential)
+        .map_err(|e| AppError::BadRequest(ErrorCode::InvalidCredential, format!("Invalid credential format: {}", e)))?;
+
+    let user = state.db.collection::<User>("users")
+        .find_one(doc! { "_id": user_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::UserNotFound, "User not found".to_string()))?;
+
+    let (passkey, decision) = authenticator_policy::finish_registration(&state, &user.username, user.role, &credential, &reg_state)?;
+
+    passkeys::store_passkey(&state.db, user.id, &user.username, &passkey, &decision).await?;
+
+    let credential_id = passkeys::encode_credential_id(passkey.cred_id());
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user.id, &user.username, security_events::ACCOUNT_RECOVERY, security_events::SUCCESS, &client)
+            .credential(&credential_id)
+            .detail("Recovery code used to register a new passkey"),
+    )
+    .await?;
+
+    recovery_challenges
+        .delete_one(doc! { "username": &body.username })
+        .await?;
+
+    let issued = refresh_token::issue_session(&state, user.id, &user.username, &user.display_name).await?;
+
+    tracing::info!(user = %user.username, "Account recovery completed");
+
+    let mut response = Json(AuthResponse {
+        success: true,
+        username: user.username,
+        display_name: user.display_name,
+        token: issued.access_token.clone(),
+        user_id: user.id.to_hex(),
+        security_warning: None,
+    }).into_response();
+
+    session::set_session_cookies(&mut response, &state.config.session, &issued.access_token, &issued.refresh_token)?;
+
+    Ok(response)
+}
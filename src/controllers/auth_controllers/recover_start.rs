@@ -0,0 +1,98 @@
+use axum::{Json, extract::State};
+use mongodb::bson::{DateTime as BsonDateTime, Document, doc};
+use webauthn_rs::prelude::*;
+
+use crate::{
+    controllers::auth_controllers::models::{RecoverStartRequest, RecoverStartResponse},
+    middleware::client_context::ClientContext,
+    models::user_models::User,
+    utils::{
+        api_tokens, authenticator_policy, passkeys, recovery_codes, refresh_token,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/recover/start",
+    tag = "auth",
+    summary = "Start account recovery with a recovery code",
+    request_body = RecoverStartRequest,
+    responses(
+        (status = 200, description = "WebAuthn credential creation options for the replacement passkey", body = RecoverStartResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
+pub async fn recover_start(
+    State(state): State<AppState>,
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<RecoverStartRequest>,
+) -> AppResult<Json<RecoverStartResponse>> {
+    state.auth_guard.check_username(&state.db, &body.username).await?;
+    state.auth_guard.ensure_not_locked(&state.db, &body.username).await?;
+
+    let invalid = || AppError::AuthenticationError(ErrorCode::InvalidCredentials, "Invalid username or recovery code".to_string());
+
+    let Some(user) = state.db.collection::<User>("users")
+        .find_one(doc! { "username": &body.username })
+        .await?
+    else {
+        state.auth_guard.record_failed_verification(&state.db, &body.username).await?;
+        return Err(invalid());
+    };
+
+    if !recovery_codes::consume_recovery_code(&state.db, user.id, &body.recovery_code).await? {
+        tracing::warn!(username = %body.username, "Failed account recovery attempt");
+        state.auth_guard.record_failed_verification(&state.db, &body.username).await?;
+        security_events::record_security_event(
+            &state.db,
+            NewSecurityEvent::new(user.id, &user.username, security_events::ACCOUNT_RECOVERY, security_events::FAILURE, &client)
+                .detail("Invalid recovery code"),
+        )
+        .await?;
+        return Err(invalid());
+    }
+
+    state.auth_guard.clear_failures(&state.db, &body.username).await?;
+
+    refresh_token::revoke_user_sessions(&state.db, user.id).await?;
+    api_tokens::revoke_user_api_tokens(&state.db, user.id).await?;
+
+    let existing_credentials = passkeys::credential_ids_for_user(&state.db, user.id).await?;
+
+    let (ccr, reg_state) = authenticator_policy::start_registration(
+        &state,
+        Uuid::new_v4(),
+        &user.username,
+        &user.display_name,
+        user.role,
+        Some(existing_credentials).filter(|ids| !ids.is_empty()),
+    )?;
+
+    let state_json = serde_json::to_string(&reg_state)?;
+
+    let recovery_challenges = state.db.collection::<Document>("recovery_challenges");
+    recovery_challenges
+        .delete_many(doc! { "username": &user.username })
+        .await?;
+    recovery_challenges
+        .insert_one(doc! {
+            "username": &user.username,
+            "user_id": user.id,
+            "state": state_json,
+            "created_at": BsonDateTime::now(),
+        })
+        .await?;
+
+    tracing::info!(user = %user.username, "Account recovery started");
+
+    Ok(Json(RecoverStartResponse {
+        options: serde_json::to_value(ccr)?,
+        remaining_recovery_codes: recovery_codes::remaining_recovery_codes(&user).saturating_sub(1),
+    }))
+}
@@ -0,0 +1,43 @@
+use axum::{Json, extract::State};
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::cookie::CookieJar;
+
+use crate::{
+    controllers::auth_controllers::models::RefreshResponse,
+    utils::{refresh_token, session, error::{AppError, AppResult, ErrorCode, ErrorResponse}},
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    tag = "auth",
+    summary = "Rotate the refresh token cookie",
+    responses(
+        (status = 200, description = "New access token issued", body = RefreshResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    cookie_jar: CookieJar,
+) -> AppResult<Response> {
+    let presented = cookie_jar
+        .get(session::REFRESH_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::NotAuthenticated, "No refresh token found".to_string()))?;
+
+    let rotated = refresh_token::rotate_refresh_token(&state, &presented).await?;
+
+    let mut response = Json(RefreshResponse {
+        success: true,
+        username: rotated.username,
+        user_id: rotated.user_id.to_hex(),
+        token: rotated.access_token.clone(),
+        expires_in: state.config.session.access_token_max_age,
+    }).into_response();
+
+    session::set_session_cookies(&mut response, &state.config.session, &rotated.access_token, &rotated.refresh_token)?;
+
+    Ok(response)
+}
@@ -0,0 +1,47 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    controllers::auth_controllers::models::RecoveryCodesResponse,
+    middleware::{auth_user::AuthUser, client_context::ClientContext},
+    utils::{
+        recovery_codes, step_up,
+        security_events::{self, NewSecurityEvent},
+        error::{AppResult, ErrorResponse},
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/recovery-codes",
+    tag = "auth",
+    summary = "Regenerate recovery codes (requires step-up)",
+    responses(
+        (status = 200, description = "New recovery codes", body = RecoveryCodesResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    user: AuthUser,
+    client: ClientContext,
+) -> AppResult<Json<RecoveryCodesResponse>> {
+    step_up::require_recent_step_up(&state, &user).await?;
+
+    let codes = recovery_codes::replace_recovery_codes(&state.db, user.id).await?;
+
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(
+            user.id,
+            &user.username,
+            security_events::RECOVERY_CODES_REGENERATED,
+            security_events::SUCCESS,
+            &client,
+        ),
+    )
+    .await?;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes: codes }))
+}
@@ -1,30 +1,47 @@
-use axum::{extract::State, Json};
+use axum::{extract::State, response::{IntoResponse, Response}, Json};
 use mongodb::{
     bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
 };
 use webauthn_rs::prelude::*;
-use base64::{engine::general_purpose::STANDARD, Engine};
 
 use crate::{
     controllers::auth_controllers::models::{RegisterFinishRequest, RegisterResponse},
-    utils::{session, error::{AppError, AppResult}},
+    middleware::client_context::ClientContext,
+    models::user_models::Role,
+    utils::{
+        authenticator_policy::{self, PendingRegistration},
+        passkeys, recovery_codes, refresh_token, session,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
     state::AppState,
 };
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/register/finish",
+    tag = "auth",
+    summary = "Finish passkey registration and sign in",
+    request_body = RegisterFinishRequest,
+    responses(
+        (status = 200, description = "Registered and signed in, session cookies set", body = RegisterResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
 pub async fn register_finish(
     State(state): State<AppState>,
-    Json(body): Json<RegisterFinishRequest>,
-) -> AppResult<Json<RegisterResponse>> {
-    if body.username.is_empty() {
-        return Err(AppError::ValidationError("Username is required".to_string()));
-    }
-
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<RegisterFinishRequest>,
+) -> AppResult<Response> {
     let register_challenge_collection = state.db.collection::<mongodb::bson::Document>("registration_challenges");
 
     let challenge_doc = register_challenge_collection
         .find_one(doc! { "username": &body.username })
         .await?
-        .ok_or_else(|| AppError::NotFound("Registration challenge not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::ChallengeNotFound, "Registration challenge not found".to_string()))?;
 
     let state_json = challenge_doc
         .get_str("state")
@@ -35,28 +52,28 @@ pub async fn register_finish(
         .map_err(|e| AppError::InternalError(format!("Failed to get display_name: {}", e)))?
         .to_string();
 
-    let reg_state: PasskeyRegistration = serde_json::from_str(state_json)?;
+    let reg_state: PendingRegistration = serde_json::from_str(state_json)?;
 
     let credential: RegisterPublicKeyCredential = serde_json::from_value(body.credential)
-        .map_err(|e| AppError::BadRequest(format!("Invalid credential format: {}", e)))?;
+        .map_err(|e| AppError::BadRequest(ErrorCode::InvalidCredential, format!("Invalid credential format: {}", e)))?;
 
-    let passkey = state.webauthn
-        .finish_passkey_registration(&credential, &reg_state)
-        .map_err(|e| AppError::WebauthnError(format!("Passkey registration failed: {}", e)))?;
+    let role = Role::User;
+    let (passkey, decision) = authenticator_policy::finish_registration(&state, &body.username, role, &credential, &reg_state)?;
 
     let users = state.db.collection::<mongodb::bson::Document>("users");
 
-    let user_id = match users
+    let (user_id, recovery_codes, event_type) = match users
         .find_one(doc! { "username": &body.username })
         .await?
     {
         Some(user) => {
-            user.get_object_id("_id")
-                .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?
-                .clone()
+            let user_id = user.get_object_id("_id")
+                .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?;
+            (user_id, Vec::new(), security_events::PASSKEY_ADDED)
         }
         None => {
             let new_id = ObjectId::new();
+            let (recovery_codes, hashed_codes) = recovery_codes::generate_recovery_codes();
             users
                 .insert_one(
                     doc! {
@@ -64,44 +81,41 @@ pub async fn register_finish(
                         "username": &body.username,
                         "display_name": &display_name,
                         "created_at": BsonDateTime::now(),
+                        "recovery_codes": mongodb::bson::to_bson(&hashed_codes)?,
+                        "role": role.as_str(),
                     },
                 )
                 .await?;
-            new_id
+            (new_id, recovery_codes, security_events::ACCOUNT_REGISTERED)
         }
     };
 
-    let credential_id_b64 = STANDARD.encode(passkey.cred_id());
-
-    let passkey_bson = mongodb::bson::to_document(&passkey)?;
-
-    let passkeys = state.db.collection::<mongodb::bson::Document>("passkeys");
+    passkeys::store_passkey(&state.db, user_id, &body.username, &passkey, &decision).await?;
 
-    passkeys
-        .insert_one(
-            doc! {
-                "credential_id": credential_id_b64,
-                "user_id": user_id,
-                "username": &body.username,
-                "passkey": passkey_bson,
-                "created_at": BsonDateTime::now(),
-                "last_used_at": BsonDateTime::now(),
-            },
-        )
-        .await?;
+    let credential_id = passkeys::encode_credential_id(passkey.cred_id());
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user_id, &body.username, event_type, security_events::SUCCESS, &client)
+            .credential(&credential_id),
+    )
+    .await?;
 
     register_challenge_collection
         .delete_one(doc! { "username": &body.username })
         .await?;
 
-    let token = session::create_token(&body.username)
-        .map_err(|e| AppError::InternalError(format!("Failed to create session token: {}", e)))?;
+    let issued = refresh_token::issue_session(&state, user_id, &body.username, &display_name).await?;
 
-    Ok(Json(RegisterResponse {
+    let mut response = Json(RegisterResponse {
         success: true,
         username: body.username,
         display_name,
-        token,
+        token: issued.access_token.clone(),
         user_id,
-    }))
-}
\ No newline at end of file
+        recovery_codes,
+    }).into_response();
+
+    session::set_session_cookies(&mut response, &state.config.session, &issued.access_token, &issued.refresh_token)?;
+
+    Ok(response)
+}
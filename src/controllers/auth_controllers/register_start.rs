@@ -6,44 +6,49 @@ use webauthn_rs::prelude::*;
 
 use crate::{
     controllers::auth_controllers::models::RegisterStartRequest,
-    utils::error::{AppError, AppResult},
+    models::user_models::Role,
+    utils::{authenticator_policy, error::{AppError, AppResult, ErrorCode, ErrorResponse}, validation::ValidatedJson},
     state::AppState,
 };
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/register/start",
+    tag = "auth",
+    summary = "Start passkey registration",
+    request_body = RegisterStartRequest,
+    responses(
+        (status = 200, description = "WebAuthn credential creation options", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+)]
 pub async fn register_start(
     State(state): State<AppState>,
-    Json(body): Json<RegisterStartRequest>,
+    ValidatedJson(body): ValidatedJson<RegisterStartRequest>,
 ) -> AppResult<Json<serde_json::Value>> {
-    if body.username.is_empty() {
-        return Err(AppError::ValidationError("Username is required".to_string()));
-    }
-
-    if body.username.len() < 3 {
-        return Err(AppError::ValidationError("Username must be at least 3 characters long".to_string()));
-    }
-
-    if body.display_name.is_empty() {
-        return Err(AppError::ValidationError("Display name is required".to_string()));
-    }
-
-    if body.display_name.len() < 2 {
-        return Err(AppError::ValidationError("Display name must be at least 2 characters long".to_string()));
-    }
+    state.auth_guard.check_username(&state.db, &body.username).await?;
 
     let users = state.db.collection::<crate::models::user_models::User>("users");
 
     let existing = users.find_one(doc! { "username": &body.username }).await?;
     
     if existing.is_some() {
-        eprintln!("Username already exists: {}", &body.username);
-        return Err(AppError::Conflict("Username already exists".to_string()));
+        tracing::info!(username = %body.username, "Registration rejected: username already exists");
+        return Err(AppError::Conflict(ErrorCode::UsernameTaken, "Username already exists".to_string()));
     }
 
     let user_unique_id = Uuid::new_v4();
 
-    let (ccr, reg_state) = state.webauthn
-        .start_passkey_registration(user_unique_id, &body.username, &body.display_name, None)
-        .map_err(|e| AppError::WebauthnError(format!("Failed to start passkey registration: {}", e)))?;
+    let (ccr, reg_state) = authenticator_policy::start_registration(
+        &state,
+        user_unique_id,
+        &body.username,
+        &body.display_name,
+        Role::User,
+        None,
+    )?;
 
     let state_json = serde_json::to_string(&reg_state)?;
 
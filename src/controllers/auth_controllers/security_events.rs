@@ -0,0 +1,42 @@
+use axum::{Json, extract::State};
+
+use crate::{
+    controllers::auth_controllers::models::SecurityEventSummary,
+    middleware::auth_user::AuthUser,
+    utils::{security_events, error::{AppResult, ErrorResponse}},
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/security-events",
+    tag = "auth",
+    summary = "Recent security events",
+    responses(
+        (status = 200, description = "Security events, newest first", body = Vec<SecurityEventSummary>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_security_events(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<SecurityEventSummary>>> {
+    let events = security_events::security_events_for_user(&state.db, user.id, 100).await?;
+
+    Ok(Json(
+        events
+            .into_iter()
+            .map(|event| SecurityEventSummary {
+                id: event.id.to_hex(),
+                event_type: event.event_type,
+                outcome: event.outcome,
+                ip: event.ip,
+                user_agent: event.user_agent,
+                credential_id: event.credential_id,
+                detail: event.detail,
+                created_at: event.created_at,
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,92 @@
+use axum::{Json, extract::State};
+use mongodb::bson::{Document, doc};
+use webauthn_rs::prelude::*;
+
+use crate::{
+    controllers::auth_controllers::models::{StepUpFinishRequest, StepUpResponse},
+    middleware::{auth_user::AuthUser, client_context::ClientContext},
+    utils::{
+        challenges, passkeys, step_up,
+        security_events::{self, NewSecurityEvent},
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/step-up/finish",
+    tag = "auth",
+    summary = "Finish step-up re-authentication",
+    request_body = StepUpFinishRequest,
+    responses(
+        (status = 200, description = "Session stepped up", body = StepUpResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn step_up_finish(
+    State(state): State<AppState>,
+    user: AuthUser,
+    client: ClientContext,
+    ValidatedJson(body): ValidatedJson<StepUpFinishRequest>,
+) -> AppResult<Json<StepUpResponse>> {
+    let step_up_challenges = state.db.collection::<Document>("step_up_challenges");
+    let challenge_doc = step_up_challenges
+        .find_one(doc! { "session_id": user.session_id, "created_at": { "$gt": challenges::issued_after() } })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::ChallengeNotFound, "Step-up challenge not found or expired".to_string()))?;
+
+    let state_json = challenge_doc
+        .get_str("state")
+        .map_err(|e| AppError::InternalError(format!("Failed to get state: {}", e)))?;
+
+    let auth_state: PasskeyAuthentication = serde_json::from_str(state_json)?;
+
+    let credential: PublicKeyCredential = serde_json::from_value(body.credential)
+        .map_err(|e| AppError::BadRequest(ErrorCode::InvalidCredential, format!("Invalid credential format: {}", e)))?;
+
+    step_up_challenges
+        .delete_one(doc! { "session_id": user.session_id })
+        .await?;
+
+    let credential_id = passkeys::encode_credential_id(credential.raw_id.as_ref());
+
+    let authenticated = match passkeys::finish_authentication(&state, &client, &credential, &auth_state).await {
+        Ok(authenticated) => authenticated,
+        Err(e) => {
+            security_events::record_security_event(
+                &state.db,
+                NewSecurityEvent::new(user.id, &user.username, security_events::STEP_UP, security_events::FAILURE, &client)
+                    .credential(&credential_id)
+                    .detail(e.to_string()),
+            )
+            .await?;
+            return Err(e);
+        }
+    };
+
+    let passkey_owner = authenticated.passkey_doc
+        .get_object_id("user_id")
+        .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?;
+
+    if passkey_owner != user.id {
+        return Err(AppError::AuthenticationError(ErrorCode::PasskeyMismatch, "Passkey does not belong to this account".to_string()));
+    }
+
+    step_up::record_step_up(&state.db, &user).await?;
+
+    security_events::record_security_event(
+        &state.db,
+        NewSecurityEvent::new(user.id, &user.username, security_events::STEP_UP, security_events::SUCCESS, &client)
+            .credential(&credential_id),
+    )
+    .await?;
+
+    Ok(Json(StepUpResponse {
+        success: true,
+        expires_in: state.config.session.step_up_max_age,
+    }))
+}
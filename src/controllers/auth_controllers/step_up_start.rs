@@ -0,0 +1,52 @@
+use axum::{Json, extract::State};
+use mongodb::bson::{DateTime as BsonDateTime, Document, doc};
+
+use crate::{
+    middleware::auth_user::AuthUser,
+    utils::{passkeys, error::{AppError, AppResult, ErrorCode, ErrorResponse}},
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/step-up/start",
+    tag = "auth",
+    summary = "Start step-up re-authentication",
+    responses(
+        (status = 200, description = "WebAuthn credential request options", body = Object),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn step_up_start(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_passkeys = passkeys::authentication_passkeys(&state, user.id).await?;
+
+    if user_passkeys.is_empty() {
+        return Err(AppError::NotFound(ErrorCode::PasskeyNotFound, "No passkeys registered for this account".to_string()));
+    }
+
+    let (rcr, auth_state) = state.webauthn
+        .start_passkey_authentication(&user_passkeys)
+        .map_err(|e| AppError::WebauthnError(format!("Failed to start step-up: {}", e)))?;
+
+    let state_json = serde_json::to_string(&auth_state)?;
+
+    let step_up_challenges = state.db.collection::<Document>("step_up_challenges");
+    step_up_challenges
+        .delete_many(doc! { "session_id": user.session_id })
+        .await?;
+    step_up_challenges
+        .insert_one(doc! {
+            "session_id": user.session_id,
+            "user_id": user.id,
+            "state": state_json,
+            "created_at": BsonDateTime::now(),
+        })
+        .await?;
+
+    Ok(Json(serde_json::to_value(rcr)?))
+}
@@ -1,2 +1,5 @@
 pub mod auth_controllers;
-pub mod poll_controllers;
\ No newline at end of file
+pub mod poll_controllers;
+pub mod admin_controllers;
+pub mod organization_controllers;
+pub mod system_controllers;
@@ -0,0 +1,126 @@
+use axum::{Json, extract::{Path, State}};
+use std::collections::HashMap;
+
+use futures::stream::TryStreamExt;
+use mongodb::bson::{Bson, Document, doc, oid::ObjectId};
+
+use crate::{
+    controllers::organization_controllers::{
+        models::{DashboardPoll, OrganizationDashboard},
+        organizations::find_organization,
+    },
+    middleware::auth_user::AuthUser,
+    models::{
+        organization_models::{Membership, OrgRole},
+        poll_models::Poll,
+        user_models::Role,
+        vote_record_models::VoteRecord,
+    },
+    utils::{
+        organizations,
+        error::{AppResult, ErrorResponse},
+    },
+    state::AppState,
+};
+
+fn rate(voters: u64, members: u64) -> f64 {
+    if members == 0 {
+        0.0
+    } else {
+        voters as f64 / members as f64
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orgs/{orgId}/dashboard",
+    tag = "organizations",
+    summary = "Organization participation dashboard",
+    params(
+        ("orgId" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Dashboard", body = OrganizationDashboard),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn get_dashboard(
+    Path(organization_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<OrganizationDashboard>> {
+    let organization = find_organization(&state, &organization_id).await?;
+
+    if user.role < Role::Admin {
+        organizations::require_membership(&state.db, organization.id, user.id, OrgRole::Member).await?;
+    }
+
+    let member_count = state.db.collection::<Membership>("memberships")
+        .count_documents(doc! { "organization_id": organization.id })
+        .await?;
+
+    let polls: Vec<Poll> = state.db.collection::<Poll>("polls")
+        .find(doc! { "organization_id": organization.id })
+        .sort(doc! { "created_at": -1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    let vote_records = state.db.collection::<VoteRecord>("vote_records");
+    let poll_ids: Vec<ObjectId> = polls.iter().map(|poll| poll.id).collect();
+
+    let active_voters = vote_records
+        .distinct("user_id", doc! { "poll_id": { "$in": &poll_ids }, "user_id": { "$ne": Bson::Null } })
+        .await?
+        .len() as u64;
+
+    let pipeline = vec![
+        doc! { "$match": { "poll_id": { "$in": &poll_ids }, "user_id": { "$ne": Bson::Null } } },
+        doc! { "$group": { "_id": { "poll_id": "$poll_id", "user_id": "$user_id" } } },
+        doc! { "$group": { "_id": "$_id.poll_id", "voters": { "$sum": 1 } } },
+    ];
+    let voters_per_poll: HashMap<ObjectId, u64> = vote_records
+        .aggregate(pipeline)
+        .await?
+        .try_collect::<Vec<Document>>()
+        .await?
+        .into_iter()
+        .filter_map(|group| {
+            let poll_id = group.get_object_id("_id").ok()?;
+            let voters = group.get_i32("voters").ok()?;
+            Some((poll_id, voters as u64))
+        })
+        .collect();
+
+    let mut dashboard_polls = Vec::with_capacity(polls.len());
+    for poll in &polls {
+        let voters = voters_per_poll.get(&poll.id).copied().unwrap_or(0);
+
+        dashboard_polls.push(DashboardPoll {
+            id: poll.id.to_hex(),
+            question: poll.question.clone(),
+            creator_id: poll.creator_id.to_hex(),
+            is_closed: poll.is_closed,
+            created_at: poll.created_at,
+            total_votes: poll.total_votes,
+            voters,
+            participation_rate: rate(voters, member_count),
+        });
+    }
+
+    Ok(Json(OrganizationDashboard {
+        organization_id: organization.id.to_hex(),
+        name: organization.name,
+        member_count,
+        poll_count: polls.len() as u64,
+        open_poll_count: polls.iter().filter(|poll| !poll.is_closed).count() as u64,
+        total_votes: polls.iter().map(|poll| poll.total_votes as i64).sum(),
+        active_voters,
+        participation_rate: rate(active_voters, member_count),
+        polls: dashboard_polls,
+    }))
+}
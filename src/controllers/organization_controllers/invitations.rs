@@ -0,0 +1,245 @@
+use axum::{Json, extract::{Path, State}};
+use chrono::{Duration, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    controllers::organization_controllers::{
+        models::{InvitationResponse, InviteMemberRequest, MemberResponse},
+        organizations::find_organization,
+    },
+    db::indexes::is_duplicate_key,
+    middleware::auth_user::AuthUser,
+    models::{
+        organization_models::{Invitation, InvitationStatus, Membership, OrgRole},
+        user_models::User,
+    },
+    utils::{
+        organizations,
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+const INVITATION_TTL_DAYS: i64 = 7;
+
+async fn find_pending_invitation(state: &AppState, invitation_id: &str, user: &AuthUser) -> AppResult<Invitation> {
+    let invitation_obj_id = ObjectId::parse_str(invitation_id)
+        .map_err(|_| AppError::invalid("invitation_id", ErrorCode::InvalidId, "Invalid invitation id"))?;
+
+    let invitation = state.db.collection::<Invitation>("invitations")
+        .find_one(doc! { "_id": invitation_obj_id, "user_id": user.id, "status": "pending" })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::InvitationNotFound, "Invitation not found".to_string()))?;
+
+    if invitation.expires_at < Utc::now() {
+        return Err(AppError::BadRequest(ErrorCode::InvitationExpired, "Invitation has expired".to_string()));
+    }
+
+    Ok(invitation)
+}
+
+async fn set_status(state: &AppState, invitation: &Invitation, status: &str) -> AppResult<()> {
+    state.db.collection::<Invitation>("invitations")
+        .update_one(
+            doc! { "_id": invitation.id },
+            doc! { "$set": { "status": status } },
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orgs/{orgId}/invitations",
+    tag = "organizations",
+    summary = "Invite a user",
+    request_body = InviteMemberRequest,
+    params(
+        ("orgId" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Invitation", body = InvitationResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn invite_member(
+    Path(organization_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<InviteMemberRequest>,
+) -> AppResult<Json<InvitationResponse>> {
+    let organization = find_organization(&state, &organization_id).await?;
+    let inviter = organizations::require_membership(&state.db, organization.id, user.id, OrgRole::Admin).await?;
+
+    let role = payload.role.unwrap_or(OrgRole::Member);
+    if role > inviter.role {
+        return Err(AppError::Forbidden(ErrorCode::OrganizationPermissionDenied, "You cannot invite members with a higher role than your own".to_string()));
+    }
+
+    let invitee = state.db.collection::<User>("users")
+        .find_one(doc! { "username": &payload.username })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::UserNotFound, format!("User '{}' not found", payload.username)))?;
+
+    if organizations::membership(&state.db, organization.id, invitee.id).await?.is_some() {
+        return Err(AppError::Conflict(ErrorCode::AlreadyMember, "User is already a member of this organization".to_string()));
+    }
+
+    let now = Utc::now();
+    let invitations = state.db.collection::<Invitation>("invitations");
+
+    let pending: Vec<Invitation> = invitations
+        .find(doc! { "organization_id": organization.id, "user_id": invitee.id, "status": "pending" })
+        .await?
+        .try_collect()
+        .await?;
+
+    if pending.iter().any(|invitation| invitation.expires_at > now) {
+        return Err(AppError::Conflict(ErrorCode::InvitationPending, "User already has a pending invitation".to_string()));
+    }
+
+    let invitation = Invitation {
+        id: ObjectId::new(),
+        organization_id: organization.id,
+        organization_name: organization.name,
+        user_id: invitee.id,
+        username: invitee.username,
+        role,
+        invited_by: user.username.clone(),
+        status: InvitationStatus::Pending,
+        created_at: now,
+        expires_at: now + Duration::days(INVITATION_TTL_DAYS),
+    };
+
+    invitations.insert_one(&invitation).await?;
+
+    tracing::info!(inviter = %user.username, invitee = %invitation.username, organization_id = %invitation.organization_id, "Organization invitation sent");
+
+    Ok(Json(InvitationResponse::from(&invitation)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orgs/invitations",
+    tag = "organizations",
+    summary = "Pending invitations for the current user",
+    responses(
+        (status = 200, description = "Invitations", body = Vec<InvitationResponse>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<InvitationResponse>>> {
+    let now = Utc::now();
+
+    let invitations: Vec<Invitation> = state.db.collection::<Invitation>("invitations")
+        .find(doc! { "user_id": user.id, "status": "pending" })
+        .sort(doc! { "created_at": -1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(Json(
+        invitations
+            .iter()
+            .filter(|invitation| invitation.expires_at > now)
+            .map(InvitationResponse::from)
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orgs/invitations/{invitationId}/accept",
+    tag = "organizations",
+    summary = "Accept an invitation",
+    params(
+        ("invitationId" = String, Path, description = "Invitation id"),
+    ),
+    responses(
+        (status = 200, description = "New membership", body = MemberResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn accept_invitation(
+    Path(invitation_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<MemberResponse>> {
+    let invitation = find_pending_invitation(&state, &invitation_id, &user).await?;
+
+    let membership = match organizations::membership(&state.db, invitation.organization_id, user.id).await? {
+        Some(existing) => existing,
+        None => {
+            let membership = Membership {
+                id: ObjectId::new(),
+                organization_id: invitation.organization_id,
+                user_id: user.id,
+                username: user.username.clone(),
+                role: invitation.role,
+                joined_at: Utc::now(),
+            };
+
+            match state.db.collection::<Membership>("memberships").insert_one(&membership).await {
+                Ok(_) => membership,
+                // Accepted concurrently from another session.
+                Err(e) if is_duplicate_key(&e) => {
+                    return Err(AppError::Conflict(ErrorCode::AlreadyMember, "You are already a member of this organization".to_string()));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    };
+
+    set_status(&state, &invitation, "accepted").await?;
+
+    tracing::info!(user = %user.username, organization_id = %invitation.organization_id, "Organization invitation accepted");
+
+    Ok(Json(MemberResponse::from(&membership)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orgs/invitations/{invitationId}/decline",
+    tag = "organizations",
+    summary = "Decline an invitation",
+    params(
+        ("invitationId" = String, Path, description = "Invitation id"),
+    ),
+    responses(
+        (status = 200, description = "Declined invitation", body = InvitationResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn decline_invitation(
+    Path(invitation_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<InvitationResponse>> {
+    let mut invitation = find_pending_invitation(&state, &invitation_id, &user).await?;
+
+    set_status(&state, &invitation, "declined").await?;
+    invitation.status = InvitationStatus::Declined;
+
+    Ok(Json(InvitationResponse::from(&invitation)))
+}
@@ -0,0 +1,76 @@
+use axum::{Json, extract::{Path, State}};
+use mongodb::bson::{doc, oid::ObjectId};
+use serde_json::json;
+
+use crate::{
+    controllers::organization_controllers::organizations::find_organization,
+    middleware::auth_user::AuthUser,
+    models::organization_models::{Membership, OrgRole},
+    utils::{
+        organizations,
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/api/orgs/{orgId}/members/{userId}",
+    tag = "organizations",
+    summary = "Remove a member or leave the organization",
+    params(
+        ("orgId" = String, Path, description = "Organization id"),
+        ("userId" = String, Path, description = "User id"),
+    ),
+    responses(
+        (status = 200, description = "Member removed", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn remove_member(
+    Path((organization_id, user_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<serde_json::Value>> {
+    let organization = find_organization(&state, &organization_id).await?;
+
+    let member_obj_id = ObjectId::parse_str(&user_id)
+        .map_err(|_| AppError::invalid("user_id", ErrorCode::InvalidId, "Invalid user id"))?;
+
+    let actor = organizations::require_membership(&state.db, organization.id, user.id, OrgRole::Member).await?;
+
+    let member = organizations::membership(&state.db, organization.id, member_obj_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::MemberNotFound, "Member not found".to_string()))?;
+
+    let leaving = member.user_id == user.id;
+    if !leaving && (actor.role < OrgRole::Admin || member.role > actor.role) {
+        return Err(AppError::Forbidden(ErrorCode::OrganizationPermissionDenied, "You do not have permission to remove this member".to_string()));
+    }
+
+    let memberships = state.db.collection::<Membership>("memberships");
+
+    if member.role == OrgRole::Owner {
+        let owners = memberships
+            .count_documents(doc! { "organization_id": organization.id, "role": "owner" })
+            .await?;
+        if owners <= 1 {
+            return Err(AppError::Conflict(ErrorCode::LastOwner, "An organization must keep at least one owner".to_string()));
+        }
+    }
+
+    memberships.delete_one(doc! { "_id": member.id }).await?;
+
+    tracing::info!(actor = %user.username, member = %member.username, organization_id = %organization.id, "Organization member removed");
+
+    Ok(Json(json!({
+        "success": true,
+        "message": if leaving { "Left organization" } else { "Member removed" }
+    })))
+}
@@ -0,0 +1,5 @@
+pub mod models;
+pub mod organizations;
+pub mod invitations;
+pub mod members;
+pub mod dashboard;
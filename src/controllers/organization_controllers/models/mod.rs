@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::organization_models::{Invitation, InvitationStatus, Membership, OrgRole};
+use crate::utils::validation::{Validate, Validator};
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InviteMemberRequest {
+    pub username: String,
+    #[serde(default)]
+    pub role: Option<OrgRole>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationSummary {
+    pub id: String,
+    pub name: String,
+    pub role: OrgRole,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberResponse {
+    pub user_id: String,
+    pub username: String,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl From<&Membership> for MemberResponse {
+    fn from(membership: &Membership) -> Self {
+        MemberResponse {
+            user_id: membership.user_id.to_hex(),
+            username: membership.username.clone(),
+            role: membership.role,
+            joined_at: membership.joined_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationDetail {
+    #[serde(flatten)]
+    pub organization: OrganizationSummary,
+    pub members: Vec<MemberResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub id: String,
+    pub organization_id: String,
+    pub organization_name: String,
+    pub username: String,
+    pub role: OrgRole,
+    pub invited_by: String,
+    pub status: InvitationStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<&Invitation> for InvitationResponse {
+    fn from(invitation: &Invitation) -> Self {
+        InvitationResponse {
+            id: invitation.id.to_hex(),
+            organization_id: invitation.organization_id.to_hex(),
+            organization_name: invitation.organization_name.clone(),
+            username: invitation.username.clone(),
+            role: invitation.role,
+            invited_by: invitation.invited_by.clone(),
+            status: invitation.status,
+            created_at: invitation.created_at,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DashboardPoll {
+    pub id: String,
+    pub question: String,
+    pub creator_id: String,
+    pub is_closed: bool,
+    pub created_at: DateTime<Utc>,
+    pub total_votes: i32,
+    pub voters: u64,
+    pub participation_rate: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationDashboard {
+    pub organization_id: String,
+    pub name: String,
+    pub member_count: u64,
+    pub poll_count: u64,
+    pub open_poll_count: u64,
+    pub total_votes: i64,
+    pub active_voters: u64,
+    pub participation_rate: f64,
+    pub polls: Vec<DashboardPoll>,
+}
+
+impl Validate for CreateOrganizationRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        let limits = v.limits();
+        v.text("name", &mut self.name, 1, limits.organization_name_max_length);
+    }
+}
+
+impl Validate for InviteMemberRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+    }
+}
@@ -0,0 +1,169 @@
+use axum::{Json, extract::{Path, State}};
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId};
+
+use crate::{
+    controllers::organization_controllers::models::{
+        CreateOrganizationRequest, MemberResponse, OrganizationDetail, OrganizationSummary,
+    },
+    middleware::auth_user::AuthUser,
+    models::organization_models::{Membership, OrgRole, Organization},
+    utils::{
+        organizations,
+        error::{AppError, AppResult, ErrorCode, ErrorResponse},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+
+pub async fn find_organization(state: &AppState, organization_id: &str) -> AppResult<Organization> {
+    let organization_obj_id = ObjectId::parse_str(organization_id)
+        .map_err(|_| AppError::invalid("organization_id", ErrorCode::InvalidId, "Invalid organization id"))?;
+
+    state.db.collection::<Organization>("organizations")
+        .find_one(doc! { "_id": organization_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::OrganizationNotFound, "Organization not found".to_string()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orgs",
+    tag = "organizations",
+    summary = "Create an organization",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 200, description = "Created organization", body = OrganizationSummary),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<CreateOrganizationRequest>,
+) -> AppResult<Json<OrganizationSummary>> {
+    let now = Utc::now();
+    let organization = Organization {
+        id: ObjectId::new(),
+        name: payload.name,
+        created_by: user.id,
+        created_at: now,
+    };
+
+    state.db.collection::<Organization>("organizations")
+        .insert_one(&organization)
+        .await?;
+
+    state.db.collection::<Membership>("memberships")
+        .insert_one(Membership {
+            id: ObjectId::new(),
+            organization_id: organization.id,
+            user_id: user.id,
+            username: user.username.clone(),
+            role: OrgRole::Owner,
+            joined_at: now,
+        })
+        .await?;
+
+    tracing::info!(user = %user.username, organization_id = %organization.id, "Organization created");
+
+    Ok(Json(OrganizationSummary {
+        id: organization.id.to_hex(),
+        name: organization.name,
+        role: OrgRole::Owner,
+        created_at: organization.created_at,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orgs",
+    tag = "organizations",
+    summary = "Organizations the current user belongs to",
+    responses(
+        (status = 200, description = "Organizations", body = Vec<OrganizationSummary>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn list_organizations(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<OrganizationSummary>>> {
+    let memberships: Vec<Membership> = state.db.collection::<Membership>("memberships")
+        .find(doc! { "user_id": user.id })
+        .await?
+        .try_collect()
+        .await?;
+
+    let organization_ids: Vec<ObjectId> = memberships.iter().map(|m| m.organization_id).collect();
+
+    let organizations: Vec<Organization> = state.db.collection::<Organization>("organizations")
+        .find(doc! { "_id": { "$in": organization_ids } })
+        .sort(doc! { "name": 1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    let summaries = organizations
+        .into_iter()
+        .filter_map(|organization| {
+            let membership = memberships.iter().find(|m| m.organization_id == organization.id)?;
+            Some(OrganizationSummary {
+                id: organization.id.to_hex(),
+                name: organization.name,
+                role: membership.role,
+                created_at: organization.created_at,
+            })
+        })
+        .collect();
+
+    Ok(Json(summaries))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orgs/{orgId}",
+    tag = "organizations",
+    summary = "Organization with members",
+    params(
+        ("orgId" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Organization", body = OrganizationDetail),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn get_organization(
+    Path(organization_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<OrganizationDetail>> {
+    let organization = find_organization(&state, &organization_id).await?;
+    let membership = organizations::require_membership(&state.db, organization.id, user.id, OrgRole::Member).await?;
+
+    let members: Vec<Membership> = state.db.collection::<Membership>("memberships")
+        .find(doc! { "organization_id": organization.id })
+        .sort(doc! { "username": 1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(Json(OrganizationDetail {
+        organization: OrganizationSummary {
+            id: organization.id.to_hex(),
+            name: organization.name,
+            role: membership.role,
+            created_at: organization.created_at,
+        },
+        members: members.iter().map(MemberResponse::from).collect(),
+    }))
+}
@@ -1,37 +1,62 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Path, State},
 };
 use chrono::Utc;
 use mongodb::{
     bson::{doc, oid::ObjectId},
 };
 
-use crate::controllers::poll_controllers::models::{CastVoteRequest, PollResponse};
+use crate::controllers::poll_controllers::models::{CastVoteRequest, PollResponse, VersionedPoll};
 use crate::models::{poll_models::Poll, vote_record_models::VoteRecord};
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::organizations;
+use crate::utils::validation::ValidatedJson;
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
-
+use crate::middleware::api_version::ApiVersion;
+
+#[utoipa::path(
+    post,
+    path = "/api/polls/{pollId}/vote",
+    tag = "polls",
+    summary = "Vote on a poll",
+    request_body = CastVoteRequest,
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Poll with updated counts", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Conflict with existing state", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:vote"])),
+)]
 pub async fn cast_vote(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-    Json(payload): Json<CastVoteRequest>,
-) -> AppResult<Json<PollResponse>> {
+    version: ApiVersion,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<CastVoteRequest>,
+) -> AppResult<Json<VersionedPoll>> {
     let poll_collection = state.db.collection::<Poll>("polls");
     let vote_collection = state.db.collection::<VoteRecord>("vote_records");
 
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|_| AppError::BadRequest("Invalid Poll id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid Poll id"))?;
 
-    let user_obj_id = ObjectId::parse_str(&claims.sub)
-        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+    let user_obj_id = user.id;
 
     let poll = poll_collection
         .find_one(doc! { "_id": poll_obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    organizations::ensure_poll_access(&state.db, &poll, Some(&user)).await?;
 
     let is_valid_option = poll
         .options
@@ -40,23 +65,26 @@ pub async fn cast_vote(
 
     if !is_valid_option {
         return Err(AppError::BadRequest(
+            ErrorCode::InvalidOption,
             "Invalid option ID for this poll".to_string(),
         ));
     }
 
     if poll.is_closed {
         return Err(AppError::BadRequest(
-            "Poll is Closed. Voting is not allowed".to_string(),
+            ErrorCode::PollClosed,
+            "Poll is closed. Voting is not allowed".to_string(),
         ));
     }
 
     let already_voted = vote_collection
-        .find_one(doc! { "poll_id": poll_obj_id, "user_id": user_obj_id.clone() })
+        .find_one(doc! { "poll_id": poll_obj_id, "user_id": user_obj_id })
         .await?;
 
     if already_voted.is_some() {
         return Err(AppError::Conflict(
-            "You have already voted for this poll and can't vote again, Bye Byee.".to_string(),
+            ErrorCode::AlreadyVoted,
+            "You have already voted in this poll".to_string(),
         ));
     }
 
@@ -72,6 +100,7 @@ pub async fn cast_vote(
 
     if update_result.matched_count == 0 {
         return Err(AppError::BadRequest(
+            ErrorCode::InvalidOption,
             "Option not found for this poll".to_string(),
         ));
     }
@@ -90,21 +119,14 @@ pub async fn cast_vote(
     };
 
     vote_collection.insert_one(vote).await?;
+    state.metrics.record_vote(poll.poll_type());
 
     let new_poll = poll_collection
         .find_one(doc! { "_id": poll_obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
-
-    let poll_res = PollResponse {
-        id: new_poll.id.to_hex(),
-        question: new_poll.question,
-        creator_id: new_poll.creator_id.to_hex(),
-        options: new_poll.options,
-        is_closed: new_poll.is_closed,
-        created_at: new_poll.created_at,
-        total_votes: new_poll.total_votes,
-    };
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    let poll_res = VersionedPoll::render(version, new_poll);
 
     Ok(Json(poll_res))
 }
\ No newline at end of file
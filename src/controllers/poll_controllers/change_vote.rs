@@ -1,36 +1,80 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Path, State},
 };
 
 use mongodb::{
     bson::{doc, oid::ObjectId},
 };
 
-use crate::{controllers::poll_controllers::models::PollResponse, models::{
+use crate::{controllers::poll_controllers::models::{PollResponse, VersionedPoll}, models::{
     poll_models::Poll,
     vote_record_models::VoteRecord,
 }};
 use crate::controllers::poll_controllers::models::CastVoteRequest;
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::organizations;
+use crate::utils::validation::ValidatedJson;
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+#[utoipa::path(
+    post,
+    path = "/api/polls/{pollId}/change/vote",
+    tag = "polls",
+    summary = "Change an existing vote",
+    request_body = CastVoteRequest,
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Poll with updated counts", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Vote unchanged, or changed concurrently", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:vote"])),
+)]
 pub async fn change_vote(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-    Json(payload): Json<CastVoteRequest>,
-) -> AppResult<Json<PollResponse>> {
+    version: ApiVersion,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<CastVoteRequest>,
+) -> AppResult<Json<VersionedPoll>> {
 
     let polls_collection = state.db.collection::<Poll>("polls");
     let vote_collection = state.db.collection::<VoteRecord>("vote_records");
 
     let obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|_| AppError::BadRequest("Invalid poll id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
 
-    let user_obj_id = ObjectId::parse_str(&claims.sub)
-        .map_err(|_| AppError::BadRequest("Invalid user id".to_string()))?;
+    let user_obj_id = user.id;
+
+    let poll = polls_collection
+        .find_one(doc! { "_id": obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    organizations::ensure_poll_access(&state.db, &poll, Some(&user)).await?;
+
+    if !poll.options.iter().any(|option| option.id == payload.option_id) {
+        return Err(AppError::BadRequest(
+            ErrorCode::InvalidOption,
+            "Invalid option ID for this poll".to_string(),
+        ));
+    }
+
+    if poll.is_closed {
+        return Err(AppError::BadRequest(
+            ErrorCode::PollClosed,
+            "Poll is closed. Voting is not allowed".to_string(),
+        ));
+    }
 
     let previous_vote = vote_collection
         .find_one(doc! {
@@ -38,58 +82,83 @@ pub async fn change_vote(
             "user_id": user_obj_id
         })
         .await?
-        .ok_or_else(|| AppError::BadRequest("User has not voted yet".to_string()))?;
+        .ok_or_else(|| AppError::BadRequest(ErrorCode::NotVoted, "User has not voted yet".to_string()))?;
 
     if previous_vote.option_id == payload.option_id {
-        return Err(AppError::Conflict("You already voted for this option".to_string()));
+        return Err(AppError::Conflict(ErrorCode::VoteUnchanged, "You already voted for this option".to_string()));
     }
 
-    polls_collection.update_one(
+    // Move the vote record first: only the request that wins this update adjusts the counts.
+    let moved = vote_collection.update_one(
+        doc! { "poll_id": obj_id, "user_id": user_obj_id, "option_id": &previous_vote.option_id },
         doc! {
-            "_id": obj_id,
-            "options.id": &previous_vote.option_id   
-        },
-        doc! {
-            "$inc": { "options.$.votes": -1 }
+            "$set": {
+                "option_id": payload.option_id.clone()
+            }
         }
     )
     .await?;
 
-    polls_collection.update_one(
+    if moved.modified_count == 0 {
+        return Err(AppError::Conflict(ErrorCode::VoteUnchanged, "Your vote changed concurrently, reload and try again".to_string()));
+    }
+
+    let decremented = polls_collection.update_one(
         doc! {
             "_id": obj_id,
-            "options.id": &payload.option_id        
+            "is_closed": false,
+            "options": { "$elemMatch": { "id": &previous_vote.option_id, "votes": { "$gt": 0 } } },
         },
         doc! {
-            "$inc": { "options.$.votes": 1 }
+            "$inc": { "options.$.votes": -1 }
         }
     )
     .await?;
 
-    vote_collection.update_one(
-        doc! { "poll_id": obj_id, "user_id": user_obj_id },
-        doc! {
-            "$set": {
-                "option_id": payload.option_id.clone()
+    let incremented = if decremented.modified_count == 1 {
+        polls_collection.update_one(
+            doc! {
+                "_id": obj_id,
+                "is_closed": false,
+                "options.id": &payload.option_id,
+            },
+            doc! {
+                "$inc": { "options.$.votes": 1 }
             }
+        )
+        .await?
+        .modified_count == 1
+    } else {
+        false
+    };
+
+    if !incremented {
+        // Put the vote back on the option that still counts it.
+        if decremented.modified_count == 1 {
+            polls_collection.update_one(
+                doc! { "_id": obj_id, "options.id": &previous_vote.option_id },
+                doc! { "$inc": { "options.$.votes": 1 } },
+            )
+            .await?;
         }
-    )
-    .await?;
+        vote_collection.update_one(
+            doc! { "poll_id": obj_id, "user_id": user_obj_id },
+            doc! { "$set": { "option_id": &previous_vote.option_id } },
+        )
+        .await?;
+
+        return Err(AppError::BadRequest(
+            ErrorCode::PollClosed,
+            "Poll is closed. Voting is not allowed".to_string(),
+        ));
+    }
 
     let new_poll = polls_collection
         .find_one(doc! { "_id": obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
-
-    let poll_response = PollResponse {
-        id: new_poll.id.to_hex(),
-        question: new_poll.question,
-        creator_id: new_poll.creator_id.to_hex(),
-        options: new_poll.options,
-        is_closed: new_poll.is_closed,
-        created_at: new_poll.created_at,
-        total_votes: new_poll.total_votes,
-    };
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    let poll_response = VersionedPoll::render(version, new_poll);
 
     Ok(Json(poll_response))
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Path, State},
 };
 use mongodb::{
     bson::{doc, oid::ObjectId},
@@ -8,21 +8,35 @@ use mongodb::{
 use serde_json::json;
 
 use crate::models::vote_record_models::VoteRecord;
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
 
+#[utoipa::path(
+    get,
+    path = "/api/polls/{pollId}/vote/check",
+    tag = "polls",
+    summary = "Whether the current user has voted",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Vote status", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:read"])),
+)]
 pub async fn check_user_vote(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
+    user: AuthUser,
 ) -> AppResult<Json<serde_json::Value>> {
     
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|_| AppError::BadRequest("Invalid poll_id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll_id"))?;
     
-    let user_obj_id = ObjectId::parse_str(&claims.sub)
-        .map_err(|_| AppError::BadRequest("Invalid user_id".to_string()))?;
+    let user_obj_id = user.id;
     
     let vote_collection = state.db.collection::<VoteRecord>("vote_records");
     
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Path, State},
 };
 
 use mongodb::{
@@ -8,31 +8,38 @@ use mongodb::{
 };
 
 use crate::models::poll_models::Poll;
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
 use crate::state::AppState;
-
+use crate::middleware::api_version::ApiVersion;
+use crate::controllers::poll_controllers::models::{PollResponse, VersionedPoll};
+
+#[utoipa::path(
+    post,
+    path = "/api/polls/{pollId}/close",
+    tag = "polls",
+    summary = "Close a poll",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Closed poll", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:manage"])),
+)]
 pub async fn close_poll(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-) -> AppResult<Json<Poll>> {
+    version: ApiVersion,
+) -> AppResult<Json<VersionedPoll>> {
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
     let poll_collection = state.db.collection::<Poll>("polls");
 
-    let poll = poll_collection
-        .find_one(doc! {"_id":poll_obj_id})
-        .await?
-        .ok_or_else(|| AppError::NotFound("The Poll id does not exist".to_string()))?;
-
-    let current_user = ObjectId::parse_str(&claims.sub)
-        .map_err(|e| AppError::BadRequest(e.to_string()))?;
-
-    if poll.creator_id != current_user {
-        return Err(AppError::BadRequest("Only the Creator of the Poll is allowed to CLOSE that Poll".to_string()));
-    }
-
     poll_collection.update_one(
         doc! { "_id": poll_obj_id },
         doc! { "$set": { "is_closed": true } },
@@ -42,7 +49,7 @@ pub async fn close_poll(
     let updated_poll = poll_collection
         .find_one(doc! { "_id": poll_obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
     
-    Ok(Json(updated_poll))
+    Ok(Json(VersionedPoll::render(version, updated_poll)))
 }
\ No newline at end of file
@@ -0,0 +1,230 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+};
+use serde_json::json;
+
+use crate::controllers::poll_controllers::models::{CollaboratorRequest, CollaboratorResponse, TransferOwnershipRequest};
+use crate::models::{
+    organization_models::OrgRole,
+    poll_models::{Poll, PollCollaborator},
+    user_models::User,
+};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::validation::ValidatedJson;
+use crate::utils::{organizations, step_up};
+use crate::middleware::auth_user::AuthUser;
+use crate::state::AppState;
+
+async fn find_poll(state: &AppState, poll_id: &str) -> AppResult<Poll> {
+    let poll_obj_id = ObjectId::parse_str(poll_id)
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
+
+    state.db.collection::<Poll>("polls")
+        .find_one(doc! { "_id": poll_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))
+}
+
+async fn find_user_by_username(state: &AppState, username: &str) -> AppResult<User> {
+    state.db.collection::<User>("users")
+        .find_one(doc! { "username": username })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::UserNotFound, format!("User '{}' not found", username)))
+}
+
+async fn require_organization_member(state: &AppState, poll: &Poll, user: &User) -> AppResult<()> {
+    let Some(organization_id) = poll.organization_id else {
+        return Ok(());
+    };
+
+    organizations::require_membership(&state.db, organization_id, user.id, OrgRole::Member)
+        .await
+        .map_err(|err| match err {
+            AppError::NotFound(..) => AppError::BadRequest(
+                ErrorCode::MemberNotFound,
+                format!("'{}' is not a member of this poll's organization", user.username),
+            ),
+            other => other,
+        })?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/polls/{pollId}/collaborators",
+    tag = "polls",
+    summary = "Add or update a collaborator",
+    request_body = CollaboratorRequest,
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Collaborators", body = Vec<CollaboratorResponse>),
+        (status = 400, description = "Invalid request, or the user is not a member of the poll's organization", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:manage"])),
+)]
+pub async fn put_collaborator(
+    Path(poll_id): Path<String>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<CollaboratorRequest>,
+) -> AppResult<Json<Vec<CollaboratorResponse>>> {
+    let mut poll = find_poll(&state, &poll_id).await?;
+    let collaborator = find_user_by_username(&state, &payload.username).await?;
+
+    if collaborator.id == poll.creator_id {
+        return Err(AppError::Conflict(ErrorCode::CollaboratorIsOwner, "The poll owner cannot be added as a collaborator".to_string()));
+    }
+
+    require_organization_member(&state, &poll, &collaborator).await?;
+
+    let mut permissions = Vec::new();
+    for permission in payload.permissions {
+        if !permissions.contains(&permission) {
+            permissions.push(permission);
+        }
+    }
+
+    let entry = PollCollaborator {
+        user_id: collaborator.id,
+        username: collaborator.username,
+        permissions,
+    };
+
+    let polls = state.db.collection::<Poll>("polls");
+
+    // Update in place so concurrent changes to other collaborators are not overwritten.
+    let updated = polls
+        .update_one(
+            doc! { "_id": poll.id, "collaborators.user_id": collaborator.id },
+            doc! { "$set": { "collaborators.$": mongodb::bson::to_bson(&entry)? } },
+        )
+        .await?;
+
+    if updated.matched_count == 0 {
+        polls
+            .update_one(
+                doc! { "_id": poll.id, "collaborators.user_id": { "$ne": collaborator.id } },
+                doc! { "$push": { "collaborators": mongodb::bson::to_bson(&entry)? } },
+            )
+            .await?;
+    }
+
+    poll.collaborators.retain(|existing| existing.user_id != collaborator.id);
+    poll.collaborators.push(entry);
+
+    Ok(Json(poll.collaborators.iter().map(CollaboratorResponse::from).collect()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/polls/{pollId}/collaborators/{userId}",
+    tag = "polls",
+    summary = "Remove a collaborator",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+        ("userId" = String, Path, description = "User id"),
+    ),
+    responses(
+        (status = 200, description = "Collaborators", body = Vec<CollaboratorResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:manage"])),
+)]
+pub async fn remove_collaborator(
+    Path((poll_id, user_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<CollaboratorResponse>>> {
+    let mut poll = find_poll(&state, &poll_id).await?;
+
+    let user_obj_id = ObjectId::parse_str(&user_id)
+        .map_err(|_| AppError::invalid("user_id", ErrorCode::InvalidId, "Invalid user id"))?;
+
+    if poll.collaborator(user_obj_id).is_none() {
+        return Err(AppError::NotFound(ErrorCode::CollaboratorNotFound, "Collaborator not found".to_string()));
+    }
+
+    poll.collaborators.retain(|existing| existing.user_id != user_obj_id);
+
+    state.db.collection::<Poll>("polls")
+        .update_one(
+            doc! { "_id": poll.id },
+            doc! { "$pull": { "collaborators": { "user_id": user_obj_id } } },
+        )
+        .await?;
+
+    Ok(Json(poll.collaborators.iter().map(CollaboratorResponse::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/polls/{pollId}/transfer",
+    tag = "polls",
+    summary = "Transfer poll ownership",
+    request_body = TransferOwnershipRequest,
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Ownership transferred", body = Object),
+        (status = 400, description = "Invalid request, or the user is not a member of the poll's organization", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed, or called with an API token", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Poll ownership changed concurrently", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn transfer_ownership(
+    Path(poll_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<TransferOwnershipRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    step_up::require_recent_step_up(&state, &user).await?;
+
+    let poll = find_poll(&state, &poll_id).await?;
+    let new_owner = find_user_by_username(&state, &payload.username).await?;
+
+    if new_owner.id == poll.creator_id {
+        return Err(AppError::Conflict(ErrorCode::CollaboratorIsOwner, "That user already owns this poll".to_string()));
+    }
+
+    require_organization_member(&state, &poll, &new_owner).await?;
+
+    let result = state.db.collection::<Poll>("polls")
+        .update_one(
+            doc! { "_id": poll.id, "creator_id": poll.creator_id },
+            doc! {
+                "$set": { "creator_id": new_owner.id },
+                "$pull": { "collaborators": { "user_id": new_owner.id } },
+            },
+        )
+        .await?;
+
+    if result.matched_count == 0 {
+        return Err(AppError::Conflict(ErrorCode::OwnershipChanged, "Poll ownership changed, reload and try again".to_string()));
+    }
+
+    tracing::info!(poll_id = %poll.id, previous_owner = %poll.creator_id, new_owner = %new_owner.username, actor = %user.username, "Poll ownership transferred");
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Poll ownership transferred",
+        "creator_id": new_owner.id.to_hex(),
+    })))
+}
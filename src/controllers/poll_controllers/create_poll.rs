@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, State},
+    extract::State,
 };
 use chrono::Utc;
 use mongodb::{
@@ -8,48 +8,54 @@ use mongodb::{
 };
 
 use crate::models::{
+    organization_models::OrgRole,
     poll_models::{Poll, PollOption}
 };
-use crate::controllers::poll_controllers::models::{CreatePollRequest, PollResponse};
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::controllers::poll_controllers::models::{CreatePollRequest, PollResponse, VersionedPoll};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::validation::ValidatedJson;
+use crate::utils::organizations;
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+#[utoipa::path(
+    post,
+    path = "/api/polls/create",
+    tag = "polls",
+    summary = "Create a poll",
+    request_body = CreatePollRequest,
+    responses(
+        (status = 200, description = "Created poll", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:create"])),
+)]
 pub async fn create_poll(
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-    Json(payload): Json<CreatePollRequest>,
-) -> AppResult<Json<PollResponse>> {
+    version: ApiVersion,
+    user: AuthUser,
+    ValidatedJson(payload): ValidatedJson<CreatePollRequest>,
+) -> AppResult<Json<VersionedPoll>> {
     
     let poll_collection = state.db.collection::<Poll>("polls");
 
     let now = Utc::now();
 
-    let unique_options: Vec<String> = (&payload.options).into_iter()
-        .map(|opt| opt.trim().to_string())
-        .collect::<Vec<String>>();
-    
-    if unique_options.len()<2 {
-        return Err(AppError::ValidationError("Enter atleast 2 options for the user to select from".to_string()));
-    }
+    let creator_id = user.id;
 
-    let mut deduped_options = Vec::new();
-    for option in &unique_options {
-        if !deduped_options.contains(option) {
-            deduped_options.push(option.clone());
+    let organization_id = match payload.organization_id.as_deref() {
+        Some(organization_id) => {
+            let organization_id = ObjectId::parse_str(organization_id)
+                .map_err(|_| AppError::invalid("organization_id", ErrorCode::InvalidId, "Invalid organization id"))?;
+            organizations::require_membership(&state.db, organization_id, creator_id, OrgRole::Member).await?;
+            Some(organization_id)
         }
-    }
-
-    if deduped_options.len() < 2 {
-        return Err(AppError::ValidationError("Poll must have at least 2 unique options".to_string()));
-    }
-
-    if deduped_options.len() != unique_options.len() {
-        return Err(AppError::ValidationError("Poll options must be unique".to_string()));
-    }
-    
-    let creator_id = ObjectId::parse_str(&claims.sub)
-        .map_err(|e| AppError::BadRequest(format!("Invalid creator_id: {}", e)))?;
+        None => None,
+    };
     
     let new_poll = Poll {
         id: ObjectId::new(),
@@ -67,22 +73,16 @@ pub async fn create_poll(
             .collect(),
         is_closed: false,
         created_at: now,
-        total_votes: 0
+        total_votes: 0,
+        collaborators: Vec::new(),
+        organization_id,
     };
 
     poll_collection.insert_one(&new_poll)
         .await?;
     
         
-    let poll_response = PollResponse {
-        id: new_poll.id.to_hex(),
-        question: new_poll.question,
-        creator_id: creator_id.to_hex(),
-        options: new_poll.options,
-        is_closed: new_poll.is_closed,
-        created_at: new_poll.created_at,
-        total_votes: new_poll.total_votes,
-    };
+    let poll_response = VersionedPoll::render(version, new_poll);
 
     Ok(Json(poll_response))
 }
\ No newline at end of file
@@ -0,0 +1,62 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+};
+use serde_json::json;
+
+use crate::models::{
+    poll_models::Poll,
+    vote_record_models::VoteRecord,
+};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::step_up;
+use crate::middleware::auth_user::AuthUser;
+use crate::state::AppState;
+
+#[utoipa::path(
+    delete,
+    path = "/api/polls/{pollId}",
+    tag = "polls",
+    summary = "Delete a poll",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Poll deleted", body = Object),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed, or called with an API token", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
+pub async fn delete_poll(
+    Path(poll_id): Path<String>,
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<serde_json::Value>> {
+    let poll_collection = state.db.collection::<Poll>("polls");
+
+    let poll_obj_id = ObjectId::parse_str(&poll_id)
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
+
+    step_up::require_recent_step_up(&state, &user).await?;
+
+    poll_collection
+        .delete_one(doc! { "_id": poll_obj_id })
+        .await?;
+
+    state.db.collection::<VoteRecord>("vote_records")
+        .delete_many(doc! { "poll_id": poll_obj_id })
+        .await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Poll deleted"
+    })))
+}
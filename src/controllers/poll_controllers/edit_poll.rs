@@ -0,0 +1,96 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use mongodb::{
+    bson::{Document, doc, oid::ObjectId},
+};
+
+use crate::controllers::poll_controllers::models::{EditPollRequest, PollResponse, VersionedPoll};
+use crate::models::poll_models::Poll;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::validation::{ValidatedJson, Validator};
+use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
+
+#[utoipa::path(
+    patch,
+    path = "/api/polls/{pollId}",
+    tag = "polls",
+    summary = "Edit a poll",
+    request_body = EditPollRequest,
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Updated poll", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 409, description = "Options cannot change once voting has started", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:manage"])),
+)]
+pub async fn edit_poll(
+    Path(poll_id): Path<String>,
+    State(state): State<AppState>,
+    version: ApiVersion,
+    ValidatedJson(payload): ValidatedJson<EditPollRequest>,
+) -> AppResult<Json<VersionedPoll>> {
+    let poll_collection = state.db.collection::<Poll>("polls");
+
+    let poll_obj_id = ObjectId::parse_str(&poll_id)
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
+
+    let mut poll = poll_collection
+        .find_one(doc! { "_id": poll_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    let mut filter = doc! { "_id": poll_obj_id };
+    let mut update = Document::new();
+
+    if let Some(question) = payload.question {
+        update.insert("question", &question);
+        poll.question = question;
+    }
+
+    if let Some(edits) = payload.options {
+        if poll.total_votes > 0 {
+            return Err(AppError::Conflict(ErrorCode::OptionsLocked, "Options cannot be edited after voting has started".to_string()));
+        }
+
+        for edit in edits {
+            let option = poll.options
+                .iter_mut()
+                .find(|option| option.id == edit.id)
+                .ok_or_else(|| AppError::BadRequest(ErrorCode::InvalidOption, format!("Invalid option ID '{}' for this poll", edit.id)))?;
+            option.text = edit.text;
+        }
+
+        let mut validator = Validator::new(&state.config.validation);
+        validator.unique("options", poll.options.iter().map(|option| option.text.as_str()));
+        validator.finish()?;
+
+        // A vote cast since the poll was read must not end up counted against a renamed option.
+        filter.insert("total_votes", 0);
+        update.insert("options", mongodb::bson::to_bson(&poll.options)?);
+    }
+
+    if !update.is_empty() {
+        let result = poll_collection
+            .update_one(filter, doc! { "$set": &update })
+            .await?;
+
+        if result.matched_count == 0 {
+            if update.contains_key("options") {
+                return Err(AppError::Conflict(ErrorCode::OptionsLocked, "Options cannot be edited after voting has started".to_string()));
+            }
+            return Err(AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()));
+        }
+    }
+
+    Ok(Json(VersionedPoll::render(version, poll)))
+}
@@ -6,33 +6,49 @@ use mongodb::{
     bson::{doc, oid::ObjectId},
 };
 
-use crate::{controllers::poll_controllers::models::PollResponse, models::{poll_models::Poll}};
-use crate::utils::error::{AppError, AppResult};
+use crate::{controllers::poll_controllers::models::{PollResponse, VersionedPoll}, models::{poll_models::Poll}};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::organizations;
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+#[utoipa::path(
+    get,
+    path = "/api/polls/{pollId}",
+    tag = "polls",
+    summary = "Get a poll",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Poll", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:read"])),
+)]
 pub async fn get_poll(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-) -> AppResult<Json<PollResponse>> {
+    version: ApiVersion,
+    user: AuthUser,
+) -> AppResult<Json<VersionedPoll>> {
 
     let poll_collection = state.db.collection::<Poll>("polls");
 
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|_| AppError::BadRequest("Invalid poll id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
     
     let poll = poll_collection
         .find_one(doc! { "_id": poll_obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
 
-    let poll_res = PollResponse {
-        id: poll.id.to_hex(),
-        question: poll.question,
-        creator_id: poll.creator_id.to_hex(),           
-        options: poll.options,
-        is_closed: poll.is_closed,
-        created_at: poll.created_at,
-        total_votes: poll.total_votes,
-    };
+    organizations::ensure_poll_access(&state.db, &poll, Some(&user)).await?;
+
+    let poll_res = VersionedPoll::render(version, poll);
     Ok(Json(poll_res))
 }
\ No newline at end of file
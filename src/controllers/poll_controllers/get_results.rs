@@ -4,6 +4,7 @@ use axum::{
     extract::{Path, State},
     response::sse::{Event, Sse},
 };
+use axum_extra::extract::cookie::CookieJar;
 use futures::stream::{self, Stream};
 use mongodb::{
     bson::{doc, oid::ObjectId},
@@ -11,40 +12,83 @@ use mongodb::{
 use tokio::time::sleep;
 
 use crate::models::poll_models::Poll;
-use crate::controllers::poll_controllers::models::PollResponse;
-use crate::utils::error::{AppError, AppResult};
+use crate::controllers::poll_controllers::models::{PollResponse, VersionedPoll};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::{organizations, refresh_token, session};
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+async fn stream_viewer(state: &AppState, cookie_jar: &CookieJar) -> Option<AuthUser> {
+    let token = cookie_jar.get(session::ACCESS_COOKIE)?;
+    let claims = session::verify_token(&state.jwt_keys, token.value()).ok()?;
+    refresh_token::ensure_session_active(&state.db, &claims.sid).await.ok()?;
+    AuthUser::try_from(&claims).ok()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/polls/{pollId}/stream",
+    tag = "polls",
+    summary = "Live poll results as server-sent events",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Event stream of poll snapshots; a final `server-restarting` event is sent before shutdown", body = PollResponse, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+)]
 pub async fn poll_updates_stream(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
+    version: ApiVersion,
+    cookie_jar: CookieJar,
 ) -> AppResult<Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>>> {
     
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|_| AppError::BadRequest("Invalid Poll id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid Poll id"))?;
+
+    let poll = state.db.collection::<Poll>("polls")
+        .find_one(doc! { "_id": poll_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
+
+    if poll.organization_id.is_some() {
+        let viewer = stream_viewer(&state, &cookie_jar).await;
+        organizations::ensure_poll_access(&state.db, &poll, viewer.as_ref()).await?;
+    }
 
     let db = Arc::clone(&state.db);
+    let connection = state.metrics.track_sse_connection();
+    let shutdown = state.shutdown.subscribe();
 
-    let stream = stream::unfold((db, poll_obj_id), |(db, poll_id)| async move {
-        sleep(Duration::from_secs(2)).await;
+    let stream = stream::unfold((db, poll_obj_id, connection, shutdown), move |(db, poll_id, connection, mut shutdown)| async move {
+        if *shutdown.borrow() {
+            return None;
+        }
+
+        let stopping = tokio::select! {
+            _ = sleep(Duration::from_secs(2)) => false,
+            _ = shutdown.wait_for(|stopping| *stopping) => true,
+        };
+
+        if stopping {
+            let event = Event::default().event("server-restarting").data(r#"{"reconnect":true}"#);
+            return Some((Ok(event), (db, poll_id, connection, shutdown)));
+        }
 
         let polls_collection = db.collection::<Poll>("polls");
         
         match polls_collection.find_one(doc! { "_id": poll_id }).await {
             Ok(Some(poll)) => {
-                let poll_response = PollResponse {
-                    id: poll.id.to_hex(),
-                    question: poll.question,
-                    creator_id: poll.creator_id.to_hex(),
-                    options: poll.options,
-                    is_closed: poll.is_closed,
-                    created_at: poll.created_at,
-                    total_votes: poll.total_votes,
-                };
+                let poll_response = VersionedPoll::render(version, poll);
 
                 match serde_json::to_string(&poll_response) {
                     Ok(json_data) => {
-                        Some((Ok(Event::default().data(json_data)), (db, poll_id)))
+                        Some((Ok(Event::default().data(json_data)), (db, poll_id, connection, shutdown)))
                     }
                     Err(_) => None,
                 }
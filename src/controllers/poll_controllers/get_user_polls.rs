@@ -1,47 +1,84 @@
 use axum::{
     Json,
-    extract::{Extension, State},
+    extract::State,
 };
 use mongodb::{
-    bson::{doc, oid::ObjectId},
+    bson::doc,
 };
 use futures::TryStreamExt;
 
-use crate::{controllers::poll_controllers::models::PollResponse, models::poll_models::Poll};
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::controllers::poll_controllers::models::{CollaboratorResponse, ManagedPollResponse, VersionedPoll};
+use crate::models::poll_models::{CollaboratorPermission, Poll};
+use crate::utils::error::{AppResult, ErrorResponse};
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+#[utoipa::path(
+    get,
+    path = "/api/polls/user/polls",
+    tag = "polls",
+    summary = "Polls the current user owns or collaborates on",
+    responses(
+        (status = 200, description = "Managed polls", body = Vec<ManagedPollResponse>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:read"])),
+)]
 pub async fn get_polls_by_user(
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-) -> AppResult<Json<Vec<PollResponse>>> {
+    version: ApiVersion,
+    user: AuthUser,
+) -> AppResult<Json<Vec<ManagedPollResponse>>> {
 
     let polls_collection = state.db.collection::<Poll>("polls");
 
-    let object_id = ObjectId::parse_str(&claims.sub)
-        .map_err(|e| AppError::BadRequest(format!("Invalid user ID: {}", e)))?;
+    let object_id = user.id;
 
     let cursor = polls_collection
-        .find(doc! { "creator_id": object_id })
+        .find(doc! {
+            "$or": [
+                { "creator_id": object_id },
+                { "collaborators.user_id": object_id },
+            ]
+        })
         .await?;
 
     let polls: Vec<Poll> = cursor
         .try_collect()
         .await?;
 
-    let poll_responses: Vec<PollResponse> = polls
+    let poll_responses: Vec<ManagedPollResponse> = polls
         .into_iter()
-        .map(|poll| PollResponse {
-            id: poll.id.to_hex(),
-            question: poll.question,
-            creator_id: poll.creator_id.to_hex(),
-            options: poll.options,
-            is_closed: poll.is_closed,
-            created_at: poll.created_at,
-            total_votes: poll.total_votes,
+        .map(|poll| {
+            let (access, permissions) = if poll.creator_id == object_id {
+                (
+                    "owner",
+                    vec![
+                        CollaboratorPermission::Close,
+                        CollaboratorPermission::Reset,
+                        CollaboratorPermission::Edit,
+                        CollaboratorPermission::ViewVoters,
+                    ],
+                )
+            } else {
+                (
+                    "collaborator",
+                    poll.collaborator(object_id)
+                        .map(|collaborator| collaborator.permissions.clone())
+                        .unwrap_or_default(),
+                )
+            };
+
+            ManagedPollResponse {
+                organization_id: matches!(version, ApiVersion::V1).then(|| poll.organization_id.map(|id| id.to_hex())),
+                access: access.to_string(),
+                permissions,
+                collaborators: poll.collaborators.iter().map(CollaboratorResponse::from).collect(),
+                poll: VersionedPoll::render(version, poll),
+            }
         })
         .collect();
 
     Ok(Json(poll_responses))
-}
\ No newline at end of file
+}
@@ -0,0 +1,70 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+};
+use std::collections::HashMap;
+
+use crate::controllers::poll_controllers::models::VoterResponse;
+use crate::models::{user_models::User, vote_record_models::VoteRecord};
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::state::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/polls/{pollId}/voters",
+    tag = "polls",
+    summary = "List who voted for what",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Voters", body = Vec<VoterResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+    ),
+    security(("session" = []), ("bearer" = ["polls:read"])),
+)]
+pub async fn get_voters(
+    Path(poll_id): Path<String>,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<VoterResponse>>> {
+    let poll_obj_id = ObjectId::parse_str(&poll_id)
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
+
+    let votes: Vec<VoteRecord> = state.db.collection::<VoteRecord>("vote_records")
+        .find(doc! { "poll_id": poll_obj_id })
+        .await?
+        .try_collect()
+        .await?;
+
+    let voter_ids: Vec<ObjectId> = votes.iter().filter_map(|vote| vote.user_id).collect();
+
+    let users: Vec<User> = state.db.collection::<User>("users")
+        .find(doc! { "_id": { "$in": &voter_ids } })
+        .await?
+        .try_collect()
+        .await?;
+
+    let usernames: HashMap<ObjectId, String> = users
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    Ok(Json(
+        votes
+            .into_iter()
+            .map(|vote| VoterResponse {
+                user_id: vote.user_id.map(|id| id.to_hex()),
+                username: vote.user_id.and_then(|id| usernames.get(&id).cloned()),
+                option_id: vote.option_id,
+                voted_at: vote.created_at,
+            })
+            .collect(),
+    ))
+}
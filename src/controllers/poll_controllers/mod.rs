@@ -9,3 +9,7 @@ pub mod models;
 pub mod polls;
 pub mod get_user_polls;
 pub mod check_vote;
+pub mod delete_poll;
+pub mod edit_poll;
+pub mod get_voters;
+pub mod collaborators;
@@ -1,14 +1,19 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use crate::models::poll_models::{PollOption};
+use utoipa::ToSchema;
+use crate::middleware::api_version::ApiVersion;
+use crate::models::poll_models::{CollaboratorPermission, Poll, PollCollaborator, PollOption};
+use crate::utils::validation::{Validate, Validator};
 
-#[derive(Deserialize,Debug)]
+#[derive(Deserialize,Debug, ToSchema)]
 pub struct CreatePollRequest {
     pub question: String,
     pub options: Vec<String>,
+    #[serde(default)]
+    pub organization_id: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PollResponse {
     pub id: String,
     pub question: String,
@@ -19,7 +24,203 @@ pub struct PollResponse {
     pub total_votes: i32
 }
 
-#[derive(Deserialize)]
+impl From<Poll> for PollResponse {
+    fn from(poll: Poll) -> Self {
+        PollResponse {
+            id: poll.id.to_hex(),
+            question: poll.question,
+            creator_id: poll.creator_id.to_hex(),
+            options: poll.options,
+            is_closed: poll.is_closed,
+            created_at: poll.created_at,
+            total_votes: poll.total_votes,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PollStatus {
+    Open,
+    Closed,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PollOptionResponse {
+    pub id: String,
+    pub text: String,
+    pub votes: u32,
+    pub percentage: f64,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PollResponseV2 {
+    pub id: String,
+    pub question: String,
+    pub creator_id: String,
+    pub organization_id: Option<String>,
+    pub poll_type: String,
+    pub status: PollStatus,
+    pub options: Vec<PollOptionResponse>,
+    pub total_votes: i32,
+    pub collaborator_count: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Poll> for PollResponseV2 {
+    fn from(poll: Poll) -> Self {
+        let total_votes = poll.total_votes;
+        let poll_type = poll.poll_type().to_string();
+        let options = poll.options
+            .into_iter()
+            .map(|option| PollOptionResponse {
+                percentage: if total_votes > 0 { option.votes as f64 * 100.0 / total_votes as f64 } else { 0.0 },
+                id: option.id,
+                text: option.text,
+                votes: option.votes,
+            })
+            .collect();
+
+        PollResponseV2 {
+            id: poll.id.to_hex(),
+            poll_type,
+            organization_id: poll.organization_id.map(|id| id.to_hex()),
+            status: if poll.is_closed { PollStatus::Closed } else { PollStatus::Open },
+            collaborator_count: poll.collaborators.len(),
+            question: poll.question,
+            creator_id: poll.creator_id.to_hex(),
+            options,
+            total_votes,
+            created_at: poll.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum VersionedPoll {
+    V1(PollResponse),
+    V2(PollResponseV2),
+}
+
+impl VersionedPoll {
+    pub fn render(version: ApiVersion, poll: Poll) -> Self {
+        match version {
+            ApiVersion::V1 => VersionedPoll::V1(poll.into()),
+            ApiVersion::V2 => VersionedPoll::V2(poll.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct CastVoteRequest {
     pub option_id: String,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EditPollRequest {
+    pub question: Option<String>,
+    pub options: Option<Vec<EditPollOption>>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EditPollOption {
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CollaboratorRequest {
+    pub username: String,
+    pub permissions: Vec<CollaboratorPermission>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TransferOwnershipRequest {
+    pub username: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CollaboratorResponse {
+    pub user_id: String,
+    pub username: String,
+    pub permissions: Vec<CollaboratorPermission>,
+}
+
+impl From<&PollCollaborator> for CollaboratorResponse {
+    fn from(collaborator: &PollCollaborator) -> Self {
+        CollaboratorResponse {
+            user_id: collaborator.user_id.to_hex(),
+            username: collaborator.username.clone(),
+            permissions: collaborator.permissions.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ManagedPollResponse {
+    #[serde(flatten)]
+    #[schema(value_type = PollResponse)]
+    pub poll: VersionedPoll,
+    /// Only set for v1, whose poll body has no organization id; v2 polls already carry it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub organization_id: Option<Option<String>>,
+    pub access: String,
+    pub permissions: Vec<CollaboratorPermission>,
+    pub collaborators: Vec<CollaboratorResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct VoterResponse {
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+    pub option_id: String,
+    pub voted_at: DateTime<Utc>,
+}
+
+impl Validate for CreatePollRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        let limits = v.limits();
+        v.text("question", &mut self.question, 1, limits.question_max_length);
+        v.count("options", self.options.len(), limits.poll_min_options, limits.poll_max_options);
+        for (index, option) in self.options.iter_mut().enumerate() {
+            v.text(format!("options[{}]", index), option, 1, limits.option_max_length);
+        }
+        v.unique("options", self.options.iter().map(String::as_str));
+    }
+}
+
+impl Validate for EditPollRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        let limits = v.limits();
+        if let Some(question) = &mut self.question {
+            v.text("question", question, 1, limits.question_max_length);
+        }
+        if let Some(options) = &mut self.options {
+            for (index, option) in options.iter_mut().enumerate() {
+                v.text(format!("options[{}].text", index), &mut option.text, 1, limits.option_max_length);
+            }
+            v.unique("options", options.iter().map(|option| option.text.as_str()));
+        }
+    }
+}
+
+impl Validate for CollaboratorRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+        v.count("permissions", self.permissions.len(), 1, usize::MAX);
+    }
+}
+
+impl Validate for TransferOwnershipRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.present("username", &self.username);
+    }
+}
+
+impl Validate for CastVoteRequest {
+    fn validate(&mut self, v: &mut Validator) {
+        v.required("option_id", &mut self.option_id);
+    }
+}
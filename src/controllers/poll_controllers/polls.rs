@@ -7,17 +7,28 @@ use mongodb::{
 };
 use futures_util::TryStreamExt;
 
-use crate::{controllers::poll_controllers::models::PollResponse, models::poll_models::Poll};
+use crate::{controllers::poll_controllers::models::{PollResponse, VersionedPoll}, models::poll_models::Poll};
 use crate::utils::error::AppResult;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
 
+#[utoipa::path(
+    get,
+    path = "/api/polls",
+    tag = "polls",
+    summary = "List public polls",
+    responses(
+        (status = 200, description = "Public polls", body = Vec<PollResponse>),
+    ),
+)]
 pub async fn get_all_polls(
     State(state): State<AppState>,
-) -> AppResult<Json<Vec<PollResponse>>> {
+    version: ApiVersion,
+) -> AppResult<Json<Vec<VersionedPoll>>> {
     let polls_collection = state.db.collection::<Poll>("polls");
 
     let mut cursor = polls_collection
-        .find(doc! {}) 
+        .find(doc! { "organization_id": null })
         .await?;
 
     let mut new_polls = Vec::new();
@@ -29,17 +40,9 @@ pub async fn get_all_polls(
         new_polls.push(poll);
     }
 
-    let poll_responses: Vec<PollResponse> = new_polls
+    let poll_responses: Vec<VersionedPoll> = new_polls
         .into_iter()
-        .map(|poll| PollResponse {
-            id: poll.id.to_hex(),
-            question: poll.question,
-            creator_id: poll.creator_id.to_hex(),
-            options: poll.options,
-            is_closed: poll.is_closed,
-            created_at: poll.created_at,
-            total_votes: poll.total_votes,
-        })
+        .map(|poll| VersionedPoll::render(version, poll))
         .collect();
 
     Ok(Json(poll_responses))
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Path, State},
 };
 
 use mongodb::{
@@ -11,31 +11,43 @@ use crate::models::{
     poll_models::Poll,
     vote_record_models::VoteRecord,
 };
-use crate::utils::error::{AppError, AppResult};
-use crate::utils::session::Claims;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+use crate::utils::step_up;
+use crate::middleware::auth_user::AuthUser;
 use crate::state::AppState;
+use crate::middleware::api_version::ApiVersion;
+use crate::controllers::poll_controllers::models::{PollResponse, VersionedPoll};
 
+#[utoipa::path(
+    post,
+    path = "/api/polls/{pollId}/reset",
+    tag = "polls",
+    summary = "Reset all votes on a poll",
+    params(
+        ("pollId" = String, Path, description = "Poll id"),
+    ),
+    responses(
+        (status = 200, description = "Reset poll", body = PollResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated, or a recent step-up is required", body = ErrorResponse),
+        (status = 403, description = "Not allowed, or called with an API token", body = ErrorResponse),
+        (status = 404, description = "Not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited, see the Retry-After header", body = ErrorResponse),
+    ),
+    security(("session" = [])),
+)]
 pub async fn reset_poll(
     Path(poll_id): Path<String>,
     State(state): State<AppState>,
-    Extension(claims): Extension<Claims>,
-) -> AppResult<Json<Poll>> {
+    version: ApiVersion,
+    user: AuthUser,
+) -> AppResult<Json<VersionedPoll>> {
     let poll_collection = state.db.collection::<Poll>("polls");
 
     let poll_obj_id = ObjectId::parse_str(&poll_id)
-        .map_err(|e| AppError::BadRequest("Invalid poll id".to_string()))?;
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
 
-    let poll = poll_collection
-        .find_one(doc! {"_id":poll_obj_id})
-        .await?
-        .ok_or_else(|| AppError::NotFound("The Poll id does not exist".to_string()))?;
-
-    let current_user = ObjectId::parse_str(&claims.sub)
-        .map_err(|e| AppError::BadRequest(format!("Invalid user id: {}", e)))?;
-
-    if poll.creator_id != current_user {
-        return Err(AppError::BadRequest("Only the Creator of the Poll is allowed to RESET that Poll".to_string()));
-    }
+    step_up::require_recent_step_up(&state, &user).await?;
 
     poll_collection.update_one(
         doc! {"_id":poll_obj_id},
@@ -58,7 +70,7 @@ pub async fn reset_poll(
     let updated_poll = poll_collection
         .find_one(doc! { "_id": poll_obj_id })
         .await?
-        .ok_or_else(|| AppError::NotFound("Poll not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()))?;
     
-    Ok(Json(updated_poll))
+    Ok(Json(VersionedPoll::render(version, updated_poll)))
 }
\ No newline at end of file
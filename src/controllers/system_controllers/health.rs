@@ -0,0 +1,113 @@
+use axum::{extract::State, http::StatusCode, Json};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::controllers::system_controllers::models::{
+    BackgroundTasksHealth, ComponentHealth, HealthStatus, LivenessResponse, ReadinessResponse,
+};
+use crate::db::connection;
+use crate::state::AppState;
+use crate::utils::background::TaskHealth;
+
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn component(started: Instant, error: Option<String>) -> ComponentHealth {
+    ComponentHealth {
+        status: if error.is_none() { HealthStatus::Ok } else { HealthStatus::Unavailable },
+        latency_ms: Some(started.elapsed().as_millis()),
+        error,
+    }
+}
+
+async fn database_health(state: &AppState) -> ComponentHealth {
+    let started = Instant::now();
+    let error = match tokio::time::timeout(DATABASE_TIMEOUT, connection::ping(&state.db)).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "Readiness check: MongoDB ping failed");
+            Some("MongoDB is unavailable".to_string())
+        }
+        Err(_) => Some("MongoDB ping timed out".to_string()),
+    };
+
+    component(started, error)
+}
+
+fn webauthn_health(state: &AppState) -> ComponentHealth {
+    let started = Instant::now();
+    let error = state.webauthn
+        .start_passkey_registration(Uuid::new_v4(), "healthcheck", "healthcheck", None)
+        .err()
+        .map(|e| {
+            tracing::warn!(error = %e, "Readiness check: failed to create a registration challenge");
+            "WebAuthn is unavailable".to_string()
+        });
+
+    component(started, error)
+}
+
+fn background_health(state: &AppState, check: fn(&TaskHealth) -> bool) -> BackgroundTasksHealth {
+    let tasks = state.background.health();
+
+    BackgroundTasksHealth {
+        status: if tasks.iter().all(check) { HealthStatus::Ok } else { HealthStatus::Unavailable },
+        tasks,
+    }
+}
+
+fn status_code(status: HealthStatus) -> StatusCode {
+    match status {
+        HealthStatus::Ok => StatusCode::OK,
+        HealthStatus::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "system",
+    summary = "Liveness probe",
+    responses(
+        (status = 200, description = "Process is alive", body = LivenessResponse),
+        (status = 503, description = "A component is unhealthy", body = LivenessResponse),
+    ),
+)]
+pub async fn healthz(State(state): State<AppState>) -> (StatusCode, Json<LivenessResponse>) {
+    // Liveness only fails when a task has died; stale tasks usually mean MongoDB is down, which readiness reports.
+    let background_tasks = background_health(&state, |task| task.running);
+    let status = background_tasks.status;
+
+    (status_code(status), Json(LivenessResponse {
+        status,
+        uptime_seconds: crate::START_TIME.elapsed().as_secs(),
+        background_tasks,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "system",
+    summary = "Readiness probe",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ReadinessResponse),
+        (status = 503, description = "A component is unhealthy", body = ReadinessResponse),
+    ),
+)]
+pub async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let database = database_health(&state).await;
+    let webauthn = webauthn_health(&state);
+    let background_tasks = background_health(&state, |task| task.healthy);
+
+    let healthy = [database.status, webauthn.status, background_tasks.status]
+        .iter()
+        .all(|status| *status == HealthStatus::Ok);
+    let status = if healthy { HealthStatus::Ok } else { HealthStatus::Unavailable };
+
+    (status_code(status), Json(ReadinessResponse {
+        status,
+        database,
+        webauthn,
+        background_tasks,
+    }))
+}
@@ -0,0 +1,44 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, header::{AUTHORIZATION, CONTENT_TYPE}},
+    response::IntoResponse,
+};
+use prometheus::TEXT_FORMAT;
+use sha2::{Digest, Sha256};
+
+use crate::state::AppState;
+use crate::utils::error::{AppError, AppResult, ErrorCode, ErrorResponse};
+
+fn authorized(headers: &HeaderMap, expected: &str) -> bool {
+    let Some(presented) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    // Compare digests so the time taken does not depend on how much of the secret matched.
+    Sha256::digest(presented.trim().as_bytes()) == Sha256::digest(expected.as_bytes())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "system",
+    summary = "Prometheus metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or wrong metrics token", body = ErrorResponse),
+    ),
+    security(("metrics" = [])),
+)]
+pub async fn metrics(State(state): State<AppState>, headers: HeaderMap) -> AppResult<impl IntoResponse> {
+    if state.config.server.metrics_token.as_deref().is_some_and(|token| !authorized(&headers, token)) {
+        return Err(AppError::AuthenticationError(ErrorCode::InvalidToken, "A valid metrics token is required".to_string()));
+    }
+
+    let body = state.metrics.render()?;
+
+    Ok(([(CONTENT_TYPE, TEXT_FORMAT)], body))
+}
@@ -0,0 +1,41 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::utils::background::TaskHealth;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Unavailable,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BackgroundTasksHealth {
+    pub status: HealthStatus,
+    pub tasks: Vec<TaskHealth>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: HealthStatus,
+    pub uptime_seconds: u64,
+    pub background_tasks: BackgroundTasksHealth,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub status: HealthStatus,
+    pub database: ComponentHealth,
+    pub webauthn: ComponentHealth,
+    pub background_tasks: BackgroundTasksHealth,
+}
@@ -1,17 +1,13 @@
 use mongodb::{options::ClientOptions, Client, Database};
-use std::env;
-use dotenvy::dotenv;
+use std::sync::Arc;
+use crate::config::DatabaseConfig;
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::metrics::Metrics;
 
-pub async fn init_db() -> AppResult<Database> {
-    dotenv().ok();
+pub async fn init_db(config: &DatabaseConfig, metrics: &Arc<Metrics>) -> AppResult<Database> {
+    let db_name = &config.db_name;
 
-    let mongo_uri = env::var("MONGO_URI")
-        .map_err(|_| AppError::InternalError("MONGO_URI must be set in .env".to_string()))?;
-    let db_name = env::var("DB_NAME")
-        .map_err(|_| AppError::InternalError("DB_NAME must be set in .env".to_string()))?;
-
-    let mut client_options = ClientOptions::parse(&mongo_uri)
+    let mut client_options = ClientOptions::parse(&config.mongo_uri)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to parse MongoDB URI: {}", e)))?;
 
@@ -21,16 +17,23 @@ pub async fn init_db() -> AppResult<Database> {
     client_options.server_selection_timeout = Some(std::time::Duration::from_secs(10));
     client_options.max_pool_size = Some(10);
     client_options.min_pool_size = Some(2);
+    client_options.command_event_handler = Some(metrics.command_event_handler());
 
     let client = Client::with_options(client_options)
         .map_err(|e| AppError::DatabaseError(format!("Failed to initialize MongoDB client: {}", e)))?;
     
-    client.database(&db_name)
-        .run_command(mongodb::bson::doc! { "ping": 1 })
-        .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to ping MongoDB: {}", e)))?;
+    let db = client.database(db_name);
+    ping(&db).await?;
     
-    println!("Database connection successful.");
+    tracing::info!(database = %db_name, "Database connection successful");
     
-    Ok(client.database(&db_name))
+    Ok(db)
+}
+
+pub async fn ping(db: &Database) -> AppResult<()> {
+    db.run_command(mongodb::bson::doc! { "ping": 1 })
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to ping MongoDB: {}", e)))?;
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use mongodb::{
+    bson::{doc, Document},
+    error::{Error as MongoError, ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Database, IndexModel,
+};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::utils::challenges::CHALLENGE_TTL;
+use crate::utils::error::AppResult;
+
+const CHALLENGE_COLLECTIONS: &[&str] = &["recovery_challenges", "step_up_challenges"];
+const INDEX_OPTIONS_CONFLICT: i32 = 85;
+const DUPLICATE_KEY: i32 = 11000;
+
+/// Whether a write was rejected by a unique index.
+pub fn is_duplicate_key(error: &MongoError) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Command(command) => command.code == DUPLICATE_KEY,
+        ErrorKind::Write(WriteFailure::WriteError(write)) => write.code == DUPLICATE_KEY,
+        _ => false,
+    }
+}
+
+fn is_options_conflict(error: &MongoError) -> bool {
+    matches!(error.kind.as_ref(), ErrorKind::Command(command) if command.code == INDEX_OPTIONS_CONFLICT)
+}
+
+async fn ensure_security_event_indexes(db: &Database, retention_days: i64) -> AppResult<()> {
+    let events = db.collection::<Document>("security_events");
+    events
+        .create_index(IndexModel::builder().keys(doc! { "user_id": 1, "created_at": -1 }).build())
+        .await?;
+
+    let retention = Duration::from_secs(retention_days as u64 * 24 * 60 * 60);
+    let ttl = IndexModel::builder()
+        .keys(doc! { "created_at": 1 })
+        .options(IndexOptions::builder().expire_after(retention).build())
+        .build();
+
+    match events.create_index(ttl).await {
+        Ok(_) => {}
+        // The retention period changed since the index was created: update it in place.
+        Err(e) if is_options_conflict(&e) => {
+            db.run_command(doc! {
+                "collMod": "security_events",
+                "index": { "keyPattern": { "created_at": 1 }, "expireAfterSeconds": retention.as_secs() as i64 },
+            })
+            .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+pub async fn ensure_indexes(db: &Database, config: &Config) -> AppResult<()> {
+    for collection in CHALLENGE_COLLECTIONS {
+        let index = IndexModel::builder()
+            .keys(doc! { "created_at": 1 })
+            .options(IndexOptions::builder().expire_after(CHALLENGE_TTL).build())
+            .build();
+        db.collection::<Document>(collection).create_index(index).await?;
+    }
+
+    for collection in ["refresh_tokens", "api_tokens"] {
+        let index = IndexModel::builder()
+            .keys(doc! { "token_hash": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build();
+        db.collection::<Document>(collection).create_index(index).await?;
+    }
+
+    // Used refresh tokens stay until they expire so reuse can still be detected.
+    for collection in ["refresh_tokens", "sessions"] {
+        let index = IndexModel::builder()
+            .keys(doc! { "expires_at": 1 })
+            .options(IndexOptions::builder().expire_after(Duration::ZERO).build())
+            .build();
+        db.collection::<Document>(collection).create_index(index).await?;
+    }
+
+    db.collection::<Document>("memberships")
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "organization_id": 1, "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        )
+        .await?;
+
+    ensure_security_event_indexes(db, config.security_event_retention_days).await?;
+
+    // Idle rate limit buckets are purged by age; budgets differ per route, so this cannot be a TTL index.
+    db.collection::<Document>("rate_limit_buckets")
+        .create_index(IndexModel::builder().keys(doc! { "updated_at": 1 }).build())
+        .await?;
+
+    tracing::info!("Database indexes ensured");
+
+    Ok(())
+}
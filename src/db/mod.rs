@@ -1 +1,2 @@
-pub mod connection;
\ No newline at end of file
+pub mod connection;
+pub mod indexes;
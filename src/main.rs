@@ -1,12 +1,20 @@
-use axum::{Router, http::{HeaderValue, Method}, response::Json, routing::get};
+use axum::{Router, http::{HeaderName, HeaderValue}, response::Json, routing::get};
 use serde_json::json;
 use std::{net::SocketAddr, sync::Arc};
 use dotenvy::dotenv;
-use tower_http::cors::CorsLayer;
-use std::time::Instant;
+use tower_http::{
+    LatencyUnit,
+    cors::{AllowOrigin, CorsLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnFailure, DefaultOnResponse, TraceLayer},
+};
+use tracing::Level;
+use middleware::{api_version::ApiVersion, request_span::REQUEST_ID_HEADER};
+use std::time::{Duration, Instant};
 use once_cell::sync::Lazy;
 static START_TIME: Lazy<Instant> = Lazy::new(Instant::now);
 
+mod config;
 mod db;
 mod routes;
 mod controllers;
@@ -19,83 +27,214 @@ mod middleware;
 async fn main() {
     dotenv().ok();
 
-    let database = match db::connection::init_db().await {
-        Ok(db) => Arc::new(db),
-        Err(e) => {
-            eprintln!("Failed to initialize database: {}", e);
+    let config = match config::Config::load() {
+        Ok(config) => Arc::new(config),
+        Err(errors) => {
+            eprint!("{}", errors);
             std::process::exit(1);
         }
     };
 
-    let webauthn = match utils::webauthn::init_webauthn() {
-        Ok(wa) => wa,
+    if std::env::args().any(|arg| arg == "--check-config") {
+        check_config(&config);
+    }
+
+    let telemetry = match utils::telemetry::init_telemetry(&config.logging) {
+        Ok(telemetry) => telemetry,
         Err(e) => {
-            eprintln!("Failed to initialize webauthn: {}", e);
+            eprintln!("Failed to initialize logging: {}", e);
             std::process::exit(1);
         }
     };
 
-    let app_state = state::AppState::new(database, webauthn);
+    for warning in &config.warnings {
+        tracing::warn!("{}", warning);
+    }
 
-    let cors_origin = std::env::var("CORS_ORIGIN")
-        .unwrap_or_else(|_| {
-            eprintln!("CORS_ORIGIN environment variable not set");
+    let metrics = match utils::metrics::init_metrics() {
+        Ok(metrics) => metrics,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to initialize metrics");
             std::process::exit(1);
-        });
+        }
+    };
 
-    let origin = cors_origin.parse::<HeaderValue>()
-        .unwrap_or_else(|_| {
-            eprintln!("Failed to parse CORS origin: {}", cors_origin);
+    let Components { webauthn, jwt_keys, authenticator_policy, auth_guard, csrf } = match init_components(&config) {
+        Ok(components) => components,
+        Err(errors) => {
+            for error in &errors.0 {
+                tracing::error!("{}", error);
+            }
             std::process::exit(1);
-        });
+        }
+    };
+
+    let database = match db::connection::init_db(&config.database, &metrics).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to initialize database");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = db::indexes::ensure_indexes(&database, &config).await {
+        tracing::error!(error = %e, "Failed to create database indexes");
+        std::process::exit(1);
+    }
+
+    if let Err(e) = utils::roles::promote_bootstrap_admins(&database, &config, &authenticator_policy).await {
+        tracing::error!(error = %e, "Failed to promote bootstrap admins");
+        std::process::exit(1);
+    }
+
+    let rate_limiter = utils::rate_limiter::init_rate_limiter(&config.rate_limit, &database);
+
+    let shutdown = utils::shutdown::init_shutdown();
+    let background = utils::background::init_background_tasks(&shutdown);
+
+    let app_state = state::AppState::new(config.clone(), database, webauthn, jwt_keys, authenticator_policy, auth_guard, csrf, rate_limiter, metrics, background, shutdown);
 
+    let cleanup_state = app_state.clone();
+    app_state.background.spawn("auth_rate_limit_cleanup", Duration::from_secs(300), move || {
+        let state = cleanup_state.clone();
+        async move { state.auth_guard.purge_expired_windows(&state.db).await }
+    });
+
+    let rate_limit_state = app_state.clone();
+    app_state.background.spawn("rate_limit_cleanup", Duration::from_secs(300), move || {
+        let state = rate_limit_state.clone();
+        async move { state.rate_limiter.purge_idle_buckets().await }
+    });
+
+    let cors_origins = config.server.cors_origins.clone();
     let cors = CorsLayer::new()
-        .allow_origin(origin)
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
-        .allow_headers([
-            axum::http::header::CONTENT_TYPE,
-            axum::http::header::ACCEPT,
-            axum::http::header::USER_AGENT,
-            axum::http::header::COOKIE,
-            axum::http::header::HeaderName::from_static("x-requested-with"),
-        ])
+        .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin.to_str().is_ok_and(|origin| utils::origins::any_matches(&cors_origins, origin))
+        }))
+        .allow_methods(config.server.cors_allowed_methods.clone())
+        .allow_headers(config.server.cors_allowed_headers.clone())
         .allow_credentials(true);
 
     let app = Router::new()
         .route("/", get(root))
-        .nest("/api/auth", routes::auth_routes::auth_routes(app_state.clone()))
-        .nest("/api/polls", routes::poll_routes::poll_routes(app_state.clone()))
-        .layer(cors);
-
-    let server_addr = std::env::var("SERVER_ADDR")
-        .unwrap_or_else(|_| {
-            eprintln!("SERVER_ADDR environment variable not set, using default 0.0.0.0:8000");
-            "0.0.0.0:8000".to_string()
-        });
-
-    let addr: SocketAddr = server_addr.parse()
-        .unwrap_or_else(|_| {
-            eprintln!("Failed to parse SERVER_ADDR: {}", server_addr);
-            std::process::exit(1);
-        });
+        .nest("/api", routes::api_routes::api_routes(app_state.clone(), ApiVersion::V1))
+        .nest("/api/v1", routes::api_routes::api_routes(app_state.clone(), ApiVersion::V1))
+        .nest("/api/v2", routes::api_routes::api_routes(app_state.clone(), ApiVersion::V2))
+        .nest("/.well-known", routes::well_known_routes::well_known_routes(app_state.clone()))
+        .merge(routes::system_routes::system_routes(app_state.clone()))
+        .merge(routes::docs_routes::docs_routes())
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), middleware::metrics::track_metrics))
+        .layer(axum::middleware::from_fn(middleware::request_span::scope_request_id))
+        .layer(cors)
+        .layer(PropagateRequestIdLayer::new(HeaderName::from_static(REQUEST_ID_HEADER)))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(middleware::request_span::make_request_span)
+                .on_request(())
+                .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Millis))
+                .on_failure(DefaultOnFailure::new().level(Level::ERROR)),
+        )
+        .layer(SetRequestIdLayer::new(HeaderName::from_static(REQUEST_ID_HEADER), MakeRequestUuid));
+
+    let addr = config.server.addr;
 
-    println!("Server running at http://{}", addr);
-    println!("CORS origin: {}", cors_origin);
+    tracing::info!(
+        %addr,
+        profile = config.profile.as_str(),
+        cors_origins = %config.server.cors_origins.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "),
+        "Server running"
+    );
 
     let listener = match tokio::net::TcpListener::bind(addr).await {
         Ok(l) => l,
         Err(e) => {
-            eprintln!("Failed to bind to address {}: {}", addr, e);
+            tracing::error!(error = %e, %addr, "Failed to bind to address");
             std::process::exit(1);
         }
     };
 
-    if let Err(e) = axum::serve(listener, app).await {
-        eprintln!("Server error: {}", e);
+    let shutdown_timeout = Duration::from_secs(config.server.shutdown_timeout);
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(utils::shutdown::wait_for_signal(app_state.shutdown.clone()));
+    let drain_deadline = async {
+        app_state.shutdown.triggered().await;
+        tokio::time::sleep(shutdown_timeout).await;
+    };
+
+    let result = tokio::select! {
+        result = server => result,
+        _ = drain_deadline => {
+            tracing::warn!(timeout_seconds = shutdown_timeout.as_secs(), "Connections still open after shutdown timeout, closing them");
+            Ok(())
+        }
+    };
+
+    app_state.shutdown.trigger();
+    app_state.background.join(shutdown_timeout).await;
+    app_state.db.client().clone().shutdown().await;
+    tracing::info!("Server stopped");
+    telemetry.shutdown();
+
+    if let Err(e) = result {
+        tracing::error!(error = %e, "Server error");
         std::process::exit(1);
     }
 }
 
+struct Components {
+    webauthn: Arc<webauthn_rs::Webauthn>,
+    jwt_keys: Arc<utils::jwt_keys::JwtKeys>,
+    authenticator_policy: Arc<utils::authenticator_policy::AuthenticatorPolicy>,
+    auth_guard: Arc<utils::auth_guard::AuthGuard>,
+    csrf: Arc<utils::csrf::CsrfPolicy>,
+}
+
+/// Builds everything that only depends on the configuration, reporting every failure rather than the first.
+fn init_components(config: &config::Config) -> Result<Components, config::ConfigErrors> {
+    let mut errors = Vec::new();
+
+    let webauthn = utils::webauthn::init_webauthn(&config.webauthn)
+        .map_err(|e| errors.push(format!("WebAuthn: {}", e)))
+        .ok();
+    let jwt_keys = utils::jwt_keys::init_jwt_keys(&config.jwt)
+        .map_err(|e| errors.push(format!("JWT keys: {}", e)))
+        .ok();
+    let authenticator_policy = utils::authenticator_policy::init_authenticator_policy(&config.webauthn)
+        .map_err(|e| errors.push(format!("Authenticator policy: {}", e)))
+        .ok();
+    let auth_guard = utils::auth_guard::init_auth_guard(&config.auth_guard)
+        .map_err(|e| errors.push(format!("Auth guard: {}", e)))
+        .ok();
+    let csrf = utils::csrf::init_csrf_policy(&config.server)
+        .map_err(|e| errors.push(format!("CSRF: {}", e)))
+        .ok();
+
+    match (webauthn, jwt_keys, authenticator_policy, auth_guard, csrf) {
+        (Some(webauthn), Some(jwt_keys), Some(authenticator_policy), Some(auth_guard), Some(csrf)) if errors.is_empty() => {
+            Ok(Components { webauthn, jwt_keys, authenticator_policy, auth_guard, csrf })
+        }
+        _ => Err(config::ConfigErrors(errors)),
+    }
+}
+
+fn check_config(config: &config::Config) -> ! {
+    for warning in &config.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    if let Err(errors) = init_components(config) {
+        eprint!("{}", errors);
+        std::process::exit(1);
+    }
+
+    println!(
+        "Configuration OK ({} profile, {})",
+        config.profile.as_str(),
+        config.source.as_deref().unwrap_or("environment only")
+    );
+    std::process::exit(0);
+}
+
 async fn root() -> Json<serde_json::Value> {
     let elapsed = START_TIME.elapsed();
     let seconds = elapsed.as_secs();
@@ -0,0 +1,79 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::convert::Infallible;
+
+use crate::utils::error::LegacyErrorResponse;
+
+pub const API_VERSION_HEADER: &str = "api-version";
+
+tokio::task_local! {
+    static CURRENT_VERSION: ApiVersion;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ApiVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "1",
+            ApiVersion::V2 => "2",
+        }
+    }
+
+    /// Path prefix the version's routes are served under.
+    pub fn path_prefix(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+
+    /// Version of the request being handled, for code that has no access to its extensions.
+    pub fn current() -> ApiVersion {
+        CURRENT_VERSION.try_with(|version| *version).unwrap_or_default()
+    }
+}
+
+pub async fn set_api_version(State(version): State<ApiVersion>, mut req: Request, next: Next) -> Response {
+    req.extensions_mut().insert(version);
+
+    let mut response = CURRENT_VERSION.scope(version, next.run(req)).await;
+
+    // v1 keeps the error body it shipped with; the coded `ErrorResponse` is v2 only.
+    if version == ApiVersion::V1
+        && let Some(legacy) = response.extensions_mut().remove::<LegacyErrorResponse>()
+    {
+        let (parts, _) = response.into_parts();
+        response = (parts, Json(legacy)).into_response();
+    }
+
+    response.headers_mut().insert(
+        HeaderName::from_static(API_VERSION_HEADER),
+        HeaderValue::from_static(version.as_str()),
+    );
+
+    response
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ApiVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<ApiVersion>().copied().unwrap_or_default())
+    }
+}
@@ -0,0 +1,22 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::middleware::client_context::ClientContext;
+use crate::state::AppState;
+use crate::utils::error::AppError;
+
+pub async fn auth_rate_limit(
+    State(state): State<AppState>,
+    client: ClientContext,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if let Some(ip) = &client.ip {
+        state.auth_guard.check_ip(&state.db, ip).await?;
+    }
+
+    Ok(next.run(req).await)
+}
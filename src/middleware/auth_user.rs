@@ -0,0 +1,67 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{Extensions, request::Parts},
+};
+use mongodb::bson::oid::ObjectId;
+
+use crate::models::user_models::Role;
+use crate::utils::{
+    api_tokens::{ApiTokenAccess, ScopeGranted},
+    error::{AppError, ErrorCode},
+    session::Claims,
+};
+
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: ObjectId,
+    pub username: String,
+    pub display_name: String,
+    pub session_id: ObjectId,
+    pub role: Role,
+}
+
+impl TryFrom<&Claims> for AuthUser {
+    type Error = AppError;
+
+    fn try_from(claims: &Claims) -> Result<Self, Self::Error> {
+        let id = ObjectId::parse_str(&claims.sub)
+            .map_err(|_| AppError::AuthenticationError(ErrorCode::InvalidToken, "Invalid user id in token".to_string()))?;
+        let session_id = ObjectId::parse_str(&claims.sid)
+            .map_err(|_| AppError::AuthenticationError(ErrorCode::InvalidToken, "Invalid session id in token".to_string()))?;
+
+        Ok(AuthUser {
+            id,
+            username: claims.username.clone(),
+            display_name: claims.display_name.clone(),
+            session_id,
+            role: claims.role,
+        })
+    }
+}
+
+impl AuthUser {
+    pub fn from_extensions(extensions: &Extensions) -> Result<Self, AppError> {
+        let claims = extensions
+            .get::<Claims>()
+            .ok_or_else(|| AppError::AuthenticationError(ErrorCode::NotAuthenticated, "Not authenticated".to_string()))?;
+
+        if extensions.get::<ApiTokenAccess>().is_some() && extensions.get::<ScopeGranted>().is_none() {
+            return Err(AppError::Forbidden(ErrorCode::ApiTokenNotAllowed, "API tokens cannot be used for this endpoint".to_string()));
+        }
+
+        AuthUser::try_from(claims)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        AuthUser::from_extensions(&parts.extensions)
+    }
+}
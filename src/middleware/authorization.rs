@@ -0,0 +1,150 @@
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use mongodb::bson::{doc, oid::ObjectId};
+use std::collections::HashMap;
+
+use crate::middleware::auth_user::AuthUser;
+use crate::models::{api_token_models::ApiScope, poll_models::{CollaboratorPermission, Poll}, user_models::Role};
+use crate::state::AppState;
+use crate::utils::{api_tokens::{ApiTokenAccess, ScopeGranted}, error::{AppError, ErrorCode}};
+
+#[derive(Debug, Clone, Copy)]
+pub enum PollAction {
+    Close,
+    Reset,
+    Edit,
+    ViewVoters,
+    Delete,
+    ManageCollaborators,
+    TransferOwnership,
+}
+
+impl PollAction {
+    fn verb(&self) -> &'static str {
+        match self {
+            PollAction::Close => "close",
+            PollAction::Reset => "reset",
+            PollAction::Edit => "edit",
+            PollAction::ViewVoters => "view the voters of",
+            PollAction::Delete => "delete",
+            PollAction::ManageCollaborators => "manage collaborators of",
+            PollAction::TransferOwnership => "transfer",
+        }
+    }
+
+    fn collaborator_permission(&self) -> Option<CollaboratorPermission> {
+        match self {
+            PollAction::Close => Some(CollaboratorPermission::Close),
+            PollAction::Reset => Some(CollaboratorPermission::Reset),
+            PollAction::Edit => Some(CollaboratorPermission::Edit),
+            PollAction::ViewVoters => Some(CollaboratorPermission::ViewVoters),
+            PollAction::Delete | PollAction::ManageCollaborators | PollAction::TransferOwnership => None,
+        }
+    }
+
+    /// Actions guarded by a step-up have no scope: API tokens cannot step up, so they are
+    /// rejected with `API_TOKEN_NOT_ALLOWED` instead of failing the step-up check.
+    fn scope(&self) -> Option<ApiScope> {
+        match self {
+            PollAction::ViewVoters => Some(ApiScope::ReadPolls),
+            PollAction::Close | PollAction::Edit | PollAction::ManageCollaborators => Some(ApiScope::ManagePolls),
+            PollAction::Delete | PollAction::Reset | PollAction::TransferOwnership => None,
+        }
+    }
+
+    fn override_role(&self) -> Role {
+        match self {
+            PollAction::Close | PollAction::Edit => Role::Moderator,
+            _ => Role::Admin,
+        }
+    }
+
+    pub fn is_allowed(&self, poll: &Poll, user: &AuthUser) -> bool {
+        let collaborator_allowed = self.collaborator_permission().is_some_and(|permission| {
+            poll.collaborator(user.id)
+                .is_some_and(|collaborator| collaborator.permissions.contains(&permission))
+        });
+
+        poll.creator_id == user.id || collaborator_allowed || user.role >= self.override_role()
+    }
+}
+
+#[derive(Clone)]
+pub struct PollPermission {
+    state: AppState,
+    action: PollAction,
+}
+
+impl PollPermission {
+    pub fn new(state: &AppState, action: PollAction) -> Self {
+        Self { state: state.clone(), action }
+    }
+}
+
+fn grant_scope(req: &mut Request, scope: ApiScope) -> Result<(), AppError> {
+    if let Some(access) = req.extensions().get::<ApiTokenAccess>() {
+        if !access.scopes.contains(&scope) {
+            return Err(AppError::Forbidden(ErrorCode::InsufficientScope, format!("API token is missing the {} scope", scope.as_str())));
+        }
+        req.extensions_mut().insert(ScopeGranted);
+    }
+
+    Ok(())
+}
+
+pub async fn require_scope(
+    State(scope): State<ApiScope>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    grant_scope(&mut req, scope)?;
+
+    Ok(next.run(req).await)
+}
+
+pub async fn require_role(
+    State(minimum): State<Role>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let user = AuthUser::from_extensions(req.extensions())?;
+
+    if user.role < minimum {
+        return Err(AppError::Forbidden(ErrorCode::InsufficientRole, format!("This action requires the {} role", minimum.as_str())));
+    }
+
+    Ok(next.run(req).await)
+}
+
+pub async fn require_poll_permission(
+    State(permission): State<PollPermission>,
+    Path(params): Path<HashMap<String, String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if let Some(scope) = permission.action.scope() {
+        grant_scope(&mut req, scope)?;
+    }
+    let user = AuthUser::from_extensions(req.extensions())?;
+
+    let poll_id = params
+        .get("pollId")
+        .ok_or_else(|| AppError::invalid("poll_id", ErrorCode::Required, "Missing poll id"))?;
+
+    let poll_obj_id = ObjectId::parse_str(poll_id)
+        .map_err(|_| AppError::invalid("poll_id", ErrorCode::InvalidId, "Invalid poll id"))?;
+
+    let poll = permission.state.db.collection::<Poll>("polls")
+        .find_one(doc! { "_id": poll_obj_id })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PollNotFound, "The Poll id does not exist".to_string()))?;
+
+    if !permission.action.is_allowed(&poll, &user) {
+        return Err(AppError::Forbidden(ErrorCode::PollPermissionDenied, format!("You are not allowed to {} this poll", permission.action.verb())));
+    }
+
+    Ok(next.run(req).await)
+}
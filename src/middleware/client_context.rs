@@ -0,0 +1,92 @@
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header::USER_AGENT, request::Parts},
+};
+use std::{convert::Infallible, net::SocketAddr};
+
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientContext {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+fn header_str<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts.headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+// Each trusted proxy appends the address it received the request from, so the client is the
+// entry `hops` places from the right; anything further left was supplied by the client.
+fn forwarded_ip(parts: &Parts, hops: usize) -> Option<String> {
+    header_str(parts, "x-forwarded-for")
+        .and_then(|value| {
+            let entries: Vec<&str> = value.split(',').collect();
+            entries.get(entries.len().saturating_sub(hops)).copied()
+        })
+        .or_else(|| header_str(parts, "x-real-ip"))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string)
+}
+
+impl ClientContext {
+    pub fn from_parts(parts: &Parts, trusted_proxy_hops: usize) -> Self {
+        let peer_ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string());
+
+        let ip = if trusted_proxy_hops > 0 {
+            forwarded_ip(parts, trusted_proxy_hops).or(peer_ip)
+        } else {
+            peer_ip
+        };
+
+        ClientContext {
+            ip,
+            user_agent: parts
+                .headers
+                .get(USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for ClientContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        Ok(ClientContext::from_parts(parts, state.config.server.trusted_proxy_hops))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn ip(forwarded_for: &str, hops: usize) -> Option<String> {
+        let (parts, _) = Request::builder()
+            .header("x-forwarded-for", forwarded_for)
+            .body(())
+            .unwrap()
+            .into_parts();
+        ClientContext::from_parts(&parts, hops).ip
+    }
+
+    #[test]
+    fn uses_the_hop_added_by_the_trusted_proxy() {
+        assert_eq!(ip("203.0.113.7", 1).as_deref(), Some("203.0.113.7"));
+        assert_eq!(ip("6.6.6.6, 203.0.113.7", 1).as_deref(), Some("203.0.113.7"));
+        assert_eq!(ip("6.6.6.6, 203.0.113.7, 10.0.0.2", 2).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn ignores_forwarding_headers_without_trusted_proxies() {
+        assert_eq!(ip("203.0.113.7", 0), None);
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Request, State},
+    http::header::{AsHeaderName, AUTHORIZATION, ORIGIN, REFERER},
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::cookie::CookieJar;
+
+use crate::state::AppState;
+use crate::utils::{csrf, error::{AppError, ErrorCode}};
+
+fn header_str(req: &Request, name: impl AsHeaderName) -> Option<&str> {
+    req.headers().get(name).and_then(|value| value.to_str().ok())
+}
+
+pub async fn csrf_protect(
+    State(state): State<AppState>,
+    cookie_jar: CookieJar,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if req.method().is_safe() || header_str(&req, AUTHORIZATION).is_some_and(|value| value.starts_with("Bearer ")) {
+        return Ok(next.run(req).await);
+    }
+
+    let source = header_str(&req, ORIGIN)
+        .filter(|origin| *origin != "null")
+        .or_else(|| header_str(&req, REFERER));
+
+    match source {
+        Some(source) if state.csrf.is_trusted_origin(source) => {}
+        Some(_) => {
+            return Err(AppError::Forbidden(ErrorCode::UntrustedOrigin, "Cross-site request blocked: untrusted origin".to_string()));
+        }
+        None => {
+            let expected = cookie_jar.get(csrf::CSRF_COOKIE).map(|cookie| cookie.value()).unwrap_or_default();
+            let presented = header_str(&req, csrf::CSRF_HEADER).unwrap_or_default();
+
+            if !csrf::tokens_match(expected, presented) {
+                return Err(AppError::Forbidden(ErrorCode::CsrfFailed, "Missing or invalid CSRF token".to_string()));
+            }
+        }
+    }
+
+    Ok(next.run(req).await)
+}
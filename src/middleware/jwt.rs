@@ -1,25 +1,53 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
     middleware::Next,
     response::Response,
 };
 use axum_extra::extract::cookie::CookieJar;
 
-use crate::utils::{error::AppError, session::verify_token};
+use crate::middleware::request_span::record_user_id;
+use crate::state::AppState;
+use crate::utils::{api_tokens, error::{AppError, ErrorCode}, refresh_token::ensure_session_active, session::verify_token};
+
+fn bearer_token(req: &Request) -> Option<&str> {
+    req.headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
 
 pub async fn jwt_auth(
+    State(state): State<AppState>,
     cookie_jar: CookieJar,
     mut req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
+    if let Some(bearer) = bearer_token(&req) {
+        if !api_tokens::is_api_token(bearer) {
+            return Err(AppError::AuthenticationError(ErrorCode::InvalidToken, "Bearer credentials must be an API token".to_string()));
+        }
+
+        let (claims, access) = api_tokens::authenticate(&state.db, bearer).await?;
+        record_user_id(&claims.sub);
+        req.extensions_mut().insert(claims);
+        req.extensions_mut().insert(access);
+
+        return Ok(next.run(req).await);
+    }
+
     let token = cookie_jar
         .get("token")
         .map(|cookie| cookie.value().to_string())
-        .ok_or_else(|| AppError::AuthenticationError("No token found".to_string()))?;
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::NotAuthenticated, "No token found".to_string()))?;
 
-    let claims = verify_token(&token)?;
+    let claims = verify_token(&state.jwt_keys, &token)?;
+    ensure_session_active(&state.db, &claims.sid).await?;
+    record_user_id(&claims.sub);
     
     req.extensions_mut().insert(claims);
     
     Ok(next.run(req).await)
-}
\ No newline at end of file
+}
@@ -1 +1,10 @@
-pub mod jwt;
\ No newline at end of file
+pub mod jwt;
+pub mod auth_user;
+pub mod client_context;
+pub mod auth_rate_limit;
+pub mod authorization;
+pub mod csrf;
+pub mod request_span;
+pub mod metrics;
+pub mod api_version;
+pub mod rate_limit;
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::middleware::client_context::ClientContext;
+use crate::state::AppState;
+use crate::utils::error::{AppError, ErrorCode};
+use crate::utils::session::Claims;
+
+const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
+#[derive(Clone)]
+pub struct RouteLimit {
+    state: AppState,
+    route: &'static str,
+}
+
+impl RouteLimit {
+    pub fn new(state: &AppState, route: &'static str) -> Self {
+        Self { state: state.clone(), route }
+    }
+}
+
+fn client_key(req: Request, trusted_proxy_hops: usize) -> (String, Request) {
+    if let Some(claims) = req.extensions().get::<Claims>() {
+        return (format!("user:{}", claims.sub), req);
+    }
+
+    let (parts, body) = req.into_parts();
+    let key = match ClientContext::from_parts(&parts, trusted_proxy_hops).ip {
+        Some(ip) => format!("ip:{}", ip),
+        None => "ip:unknown".to_string(),
+    };
+
+    (key, Request::from_parts(parts, body))
+}
+
+pub async fn rate_limit(
+    State(limit): State<RouteLimit>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let limiter = &limit.state.rate_limiter;
+    let Some(budget) = limiter.budget(limit.route).filter(|_| limiter.is_enabled()) else {
+        return next.run(req).await;
+    };
+
+    let (key, req) = client_key(req, limit.state.config.server.trusted_proxy_hops);
+    let decision = match limiter.take(limit.route, budget, &key).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            // Fail open: an unreachable bucket store must not take the limited routes down with it.
+            tracing::warn!(route = limit.route, error = %e, "Rate limit store unavailable, allowing request");
+            return next.run(req).await;
+        }
+    };
+
+    let mut response = if decision.allowed {
+        next.run(req).await
+    } else {
+        tracing::info!(route = limit.route, client = %key, retry_after = decision.retry_after, "Rate limit exceeded");
+        AppError::RateLimited {
+            code: ErrorCode::TooManyRequests,
+            message: "Too many requests, slow down".to_string(),
+            retry_after: decision.retry_after,
+        }
+        .into_response()
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(LIMIT_HEADER, HeaderValue::from(decision.limit));
+    headers.insert(REMAINING_HEADER, HeaderValue::from(decision.remaining));
+
+    response
+}
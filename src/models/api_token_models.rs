@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use mongodb::bson::{oid::ObjectId};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum ApiScope {
+    #[serde(rename = "polls:read")]
+    ReadPolls,
+    #[serde(rename = "polls:vote")]
+    Vote,
+    #[serde(rename = "polls:create")]
+    CreatePolls,
+    /// Close, edit and manage collaborators. Deleting, resetting and transferring a poll
+    /// need a step-up, which API tokens cannot perform.
+    #[serde(rename = "polls:manage")]
+    ManagePolls,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ReadPolls => "polls:read",
+            ApiScope::Vote => "polls:vote",
+            ApiScope::CreatePolls => "polls:create",
+            ApiScope::ManagePolls => "polls:manage",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiToken {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub user_id: ObjectId,
+    pub name: String,
+    pub token_hash: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -1,3 +1,8 @@
 pub mod user_models;
 pub mod poll_models;
 pub mod vote_record_models;
+pub mod session_models;
+pub mod security_event_models;
+pub mod organization_models;
+
+pub mod api_token_models;
\ No newline at end of file
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use mongodb::bson::{oid::ObjectId};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Organization {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub name: String,
+    pub created_by: ObjectId,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgRole {
+    Member,
+    Admin,
+    Owner,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Membership {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub organization_id: ObjectId,
+    pub user_id: ObjectId,
+    pub username: String,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invitation {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub organization_id: ObjectId,
+    pub organization_name: String,
+    pub user_id: ObjectId,
+    pub username: String,
+    pub role: OrgRole,
+    pub invited_by: String,
+    pub status: InvitationStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
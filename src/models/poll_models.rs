@@ -1,24 +1,67 @@
 use mongodb::bson::{oid::ObjectId};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use chrono::{DateTime, Utc};
 
+#[derive(Serialize, ToSchema)]
+#[schema(as = ObjectId)]
+#[allow(dead_code)]
+pub struct ObjectIdSchema {
+    #[serde(rename = "$oid")]
+    pub oid: String,
+}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Poll {
     #[serde(rename = "_id")]
+    #[schema(value_type = ObjectIdSchema)]
     pub id: ObjectId,
     pub question: String,
+    #[schema(value_type = ObjectIdSchema)]
     pub creator_id: ObjectId,
     pub options: Vec<PollOption>,
     pub is_closed: bool,
     pub created_at: DateTime<Utc>,
-    pub total_votes: i32
+    pub total_votes: i32,
+    #[serde(default)]
+    pub collaborators: Vec<PollCollaborator>,
+    #[serde(default)]
+    #[schema(value_type = Option<ObjectIdSchema>)]
+    pub organization_id: Option<ObjectId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct PollCollaborator {
+    #[schema(value_type = ObjectIdSchema)]
+    pub user_id: ObjectId,
+    pub username: String,
+    pub permissions: Vec<CollaboratorPermission>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollaboratorPermission {
+    Close,
+    Reset,
+    Edit,
+    ViewVoters,
+}
+
+impl Poll {
+    pub fn collaborator(&self, user_id: ObjectId) -> Option<&PollCollaborator> {
+        self.collaborators.iter().find(|collaborator| collaborator.user_id == user_id)
+    }
+
+    pub fn poll_type(&self) -> &'static str {
+        if self.organization_id.is_some() { "organization" } else { "public" }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct PollOption {
     pub id: String,
     pub text: String,
     pub votes: u32,
+    #[schema(value_type = ObjectIdSchema)]
     pub voter: ObjectId
 }
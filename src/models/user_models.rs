@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use mongodb::bson::{oid::ObjectId};
 use chrono::{DateTime, Utc};
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -6,5 +7,36 @@ pub struct User {
     #[serde(rename = "_id")]
     pub id: ObjectId,
     pub username: String,
+    pub display_name: String,
+    #[serde(with = "mongodb::bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub recovery_codes: Vec<RecoveryCode>,
+    #[serde(default)]
+    pub role: Role,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecoveryCode {
+    pub code_hash: String,
+    pub used_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,16 @@
+use axum::{Router, routing::{get, put, delete}, middleware};
+use crate::controllers::admin_controllers::users;
+use crate::middleware::{authorization::require_role, csrf::csrf_protect, jwt::jwt_auth};
+use crate::models::user_models::Role;
+use crate::state::AppState;
+
+pub fn admin_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/users", get(users::list_users))
+        .route("/users/:userId/role", put(users::update_user_role))
+        .route("/users/:userId", delete(users::delete_user))
+        .route_layer(middleware::from_fn_with_state(Role::Admin, require_role))
+        .route_layer(middleware::from_fn_with_state(state.clone(), jwt_auth))
+        .route_layer(middleware::from_fn_with_state(state.clone(), csrf_protect))
+        .with_state(state)
+}
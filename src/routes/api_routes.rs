@@ -0,0 +1,13 @@
+use axum::{Router, middleware};
+use crate::middleware::api_version::{ApiVersion, set_api_version};
+use crate::routes::{admin_routes, auth_routes, organization_routes, poll_routes};
+use crate::state::AppState;
+
+pub fn api_routes(state: AppState, version: ApiVersion) -> Router {
+    Router::new()
+        .nest("/auth", auth_routes::auth_routes(state.clone()))
+        .nest("/polls", poll_routes::poll_routes(state.clone()))
+        .nest("/admin", admin_routes::admin_routes(state.clone()))
+        .nest("/orgs", organization_routes::organization_routes(state))
+        .layer(middleware::from_fn_with_state(version, set_api_version))
+}
@@ -1,13 +1,34 @@
-use axum::{Router, routing::post};
-use crate::controllers::auth_controllers::{auth_finish, auth_start, register_finish, register_start, logout};
+use axum::{Router, routing::{delete, get, post}, middleware};
+use crate::controllers::auth_controllers::{
+    auth_finish, auth_start, register_finish, register_start, logout, refresh, me,
+    recover_start, recover_finish, regenerate_recovery_codes, step_up_start, step_up_finish, passkeys,
+    security_events, api_tokens, csrf_token,
+};
 use crate::state::AppState;
 
 pub fn auth_routes(state: AppState) -> Router {
     Router::new()
+        .route("/me", get(me::me))
+        .route("/recovery-codes", post(regenerate_recovery_codes::regenerate_recovery_codes))
+        .route("/step-up/start", post(step_up_start::step_up_start))
+        .route("/step-up/finish", post(step_up_finish::step_up_finish))
+        .route("/passkeys", get(passkeys::list_passkeys))
+        .route("/passkeys/:passkeyId", delete(passkeys::revoke_passkey))
+        .route("/security-events", get(security_events::list_security_events))
+        .route("/api-tokens", get(api_tokens::list_api_tokens))
+        .route("/api-tokens", post(api_tokens::create_api_token))
+        .route("/api-tokens/:tokenId", delete(api_tokens::revoke_api_token))
+        .route_layer(middleware::from_fn_with_state(state.clone(), crate::middleware::jwt::jwt_auth))
         .route("/register/start", post(register_start::register_start))
         .route("/register/finish", post(register_finish::register_finish))
         .route("/login/start", post(auth_start::auth_start))
         .route("/login/finish", post(auth_finish::auth_finish))
+        .route("/recover/start", post(recover_start::recover_start))
+        .route("/recover/finish", post(recover_finish::recover_finish))
+        .route("/refresh", post(refresh::refresh))
         .route("/logout", post(logout::logout))
+        .route("/csrf", get(csrf_token::csrf_token))
+        .layer(middleware::from_fn_with_state(state.clone(), crate::middleware::csrf::csrf_protect))
+        .layer(middleware::from_fn_with_state(state.clone(), crate::middleware::auth_rate_limit::auth_rate_limit))
         .with_state(state)
 }
\ No newline at end of file
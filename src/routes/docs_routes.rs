@@ -0,0 +1,144 @@
+use axum::Router;
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::controllers::admin_controllers::users;
+use crate::controllers::auth_controllers::{
+    api_tokens, auth_finish, auth_start, csrf_token, jwks, logout, me, passkeys, recover_finish, recover_start,
+    refresh, regenerate_recovery_codes, register_finish, register_start, security_events, step_up_finish, step_up_start,
+};
+use crate::controllers::organization_controllers::{dashboard, invitations, members, organizations};
+use crate::controllers::poll_controllers::{
+    cast_vote, change_vote, check_vote, close_poll, collaborators, create_poll, delete_poll, edit_poll, get_poll,
+    get_results, get_user_polls, get_voters, polls, reset_poll,
+};
+use crate::controllers::poll_controllers::models::PollResponseV2;
+use crate::controllers::system_controllers::{health, metrics};
+use crate::utils::error::LegacyErrorResponse;
+use crate::utils::session::ACCESS_COOKIE;
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                ACCESS_COOKIE,
+                "Access token cookie set by the sign-in endpoints. Unsafe requests also need a trusted Origin or the X-CSRF-Token header from GET /api/auth/csrf.",
+            ))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("A personal API token (`pat_...`), limited to the scopes listed on each operation"))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "metrics",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("The METRICS_TOKEN configured on the server"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+struct VersionedPaths;
+
+impl Modify for VersionedPaths {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let paths = std::mem::take(&mut openapi.paths.paths);
+        openapi.paths.paths = paths
+            .into_iter()
+            .map(|(path, item)| match path.strip_prefix("/api/") {
+                Some(rest) => (format!("/api/v1/{}", rest), item),
+                None => (path, item),
+            })
+            .collect();
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Polling API", description = "Passkey-authenticated polling backend. The unversioned `/api/...` paths are aliases of `/api/v1/...`; `/api/v2/...` serves the same endpoints with polls rendered as `PollResponseV2`. v2 errors are returned as `ErrorResponse`, with a stable `error` code, field-level `details` for validation failures and the `request_id` of the failed request; v1 errors keep the `LegacyErrorResponse` shape."),
+    paths(
+        register_start::register_start,
+        register_finish::register_finish,
+        auth_start::auth_start,
+        auth_finish::auth_finish,
+        recover_start::recover_start,
+        recover_finish::recover_finish,
+        refresh::refresh,
+        logout::logout,
+        csrf_token::csrf_token,
+        me::me,
+        regenerate_recovery_codes::regenerate_recovery_codes,
+        step_up_start::step_up_start,
+        step_up_finish::step_up_finish,
+        passkeys::list_passkeys,
+        passkeys::revoke_passkey,
+        security_events::list_security_events,
+        api_tokens::list_api_tokens,
+        api_tokens::create_api_token,
+        api_tokens::revoke_api_token,
+        jwks::jwks,
+        polls::get_all_polls,
+        create_poll::create_poll,
+        get_user_polls::get_polls_by_user,
+        get_poll::get_poll,
+        edit_poll::edit_poll,
+        delete_poll::delete_poll,
+        cast_vote::cast_vote,
+        change_vote::change_vote,
+        check_vote::check_user_vote,
+        close_poll::close_poll,
+        reset_poll::reset_poll,
+        get_voters::get_voters,
+        collaborators::put_collaborator,
+        collaborators::remove_collaborator,
+        collaborators::transfer_ownership,
+        get_results::poll_updates_stream,
+        organizations::list_organizations,
+        organizations::create_organization,
+        organizations::get_organization,
+        dashboard::get_dashboard,
+        invitations::invite_member,
+        invitations::list_invitations,
+        invitations::accept_invitation,
+        invitations::decline_invitation,
+        members::remove_member,
+        users::list_users,
+        users::update_user_role,
+        users::delete_user,
+        health::healthz,
+        health::readyz,
+        metrics::metrics,
+    ),
+    components(schemas(PollResponseV2, LegacyErrorResponse)),
+    modifiers(&SecuritySchemes, &VersionedPaths),
+    tags(
+        (name = "auth", description = "Passkey sign-in, sessions and account security"),
+        (name = "polls", description = "Polls, votes and live results"),
+        (name = "organizations", description = "Organizations, memberships and invitations"),
+        (name = "admin", description = "User administration"),
+        (name = "system", description = "Health checks and metrics"),
+    ),
+)]
+pub struct ApiDoc;
+
+pub fn docs_routes() -> Router {
+    Router::new().merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()))
+}
@@ -1,3 +1,9 @@
 pub mod auth_routes;
 pub mod poll_routes;
-pub mod db;
\ No newline at end of file
+pub mod well_known_routes;
+pub mod admin_routes;
+pub mod db;
+pub mod organization_routes;
+pub mod system_routes;
+pub mod docs_routes;
+pub mod api_routes;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use axum::{Router, routing::{get, post, delete}, middleware};
+use crate::controllers::organization_controllers::{dashboard, invitations, members, organizations};
+use crate::middleware::{csrf::csrf_protect, jwt::jwt_auth, rate_limit::{RouteLimit, rate_limit}};
+use crate::state::AppState;
+
+pub fn organization_routes(state: AppState) -> Router {
+    let limited = |route: &'static str| middleware::from_fn_with_state(RouteLimit::new(&state, route), rate_limit);
+
+    Router::new()
+        .route("/", get(organizations::list_organizations))
+        .route("/", post(organizations::create_organization).route_layer(limited("orgs.manage")))
+        .route("/invitations", get(invitations::list_invitations))
+        .route("/invitations/:invitationId/accept", post(invitations::accept_invitation))
+        .route("/invitations/:invitationId/decline", post(invitations::decline_invitation))
+        .route("/:orgId", get(organizations::get_organization))
+        .route("/:orgId/dashboard", get(dashboard::get_dashboard))
+        .route("/:orgId/invitations", post(invitations::invite_member).route_layer(limited("orgs.manage")))
+        .route("/:orgId/members/:userId", delete(members::remove_member).route_layer(limited("orgs.manage")))
+        .layer(middleware::from_fn_with_state(state.clone(), jwt_auth))
+        .layer(middleware::from_fn_with_state(state.clone(), csrf_protect))
+        .with_state(state)
+}
@@ -1,19 +1,36 @@
-use axum::{Router, routing::{get,post}, middleware};
-use crate::controllers::poll_controllers::{cast_vote, change_vote, check_vote, close_poll, create_poll, get_poll, get_results, get_user_polls, polls, reset_poll};
+use axum::{Router, routing::{delete, get, patch, post, put}, middleware};
+use crate::controllers::poll_controllers::{cast_vote, change_vote, check_vote, close_poll, create_poll, get_poll, get_results, get_user_polls, polls, reset_poll, delete_poll, edit_poll, get_voters, collaborators};
+use crate::middleware::authorization::{PollAction, PollPermission, require_poll_permission, require_scope};
+use crate::middleware::rate_limit::{RouteLimit, rate_limit};
+use crate::models::api_token_models::ApiScope;
 use crate::state::AppState;
 
 pub fn poll_routes(state: AppState) -> Router {
+    let permit = |action: PollAction| {
+        middleware::from_fn_with_state(PollPermission::new(&state, action), require_poll_permission)
+    };
+    let scoped = |scope: ApiScope| middleware::from_fn_with_state(scope, require_scope);
+    let limited = |route: &'static str| middleware::from_fn_with_state(RouteLimit::new(&state, route), rate_limit);
+
     Router::new()
-        .route("/create", post(create_poll::create_poll))
-        .route("/:pollId", get(get_poll::get_poll))
-        .route("/:pollId/vote", post(cast_vote::cast_vote))
-        .route("/:pollId/close", post(close_poll::close_poll))
-        .route("/:pollId/reset", post(reset_poll::reset_poll))
-        .route("/:pollId/change/vote", post(change_vote::change_vote))
-        .route("/user/polls", get(get_user_polls::get_polls_by_user))
-        .route("/:pollId/vote/check", get(check_vote::check_user_vote))
-        .layer(middleware::from_fn(crate::middleware::jwt::jwt_auth))
+        .route("/create", post(create_poll::create_poll).route_layer(scoped(ApiScope::CreatePolls)).route_layer(limited("polls.create")))
+        .route("/:pollId", get(get_poll::get_poll).route_layer(scoped(ApiScope::ReadPolls)))
+        .route("/:pollId", delete(delete_poll::delete_poll).route_layer(permit(PollAction::Delete)).route_layer(limited("polls.manage")))
+        .route("/:pollId", patch(edit_poll::edit_poll).route_layer(permit(PollAction::Edit)).route_layer(limited("polls.manage")))
+        .route("/:pollId/vote", post(cast_vote::cast_vote).route_layer(scoped(ApiScope::Vote)).route_layer(limited("polls.vote")))
+        .route("/:pollId/close", post(close_poll::close_poll).route_layer(permit(PollAction::Close)).route_layer(limited("polls.manage")))
+        .route("/:pollId/reset", post(reset_poll::reset_poll).route_layer(permit(PollAction::Reset)).route_layer(limited("polls.manage")))
+        .route("/:pollId/voters", get(get_voters::get_voters).route_layer(permit(PollAction::ViewVoters)))
+        .route("/:pollId/collaborators", put(collaborators::put_collaborator).route_layer(permit(PollAction::ManageCollaborators)).route_layer(limited("polls.manage")))
+        .route("/:pollId/collaborators/:userId", delete(collaborators::remove_collaborator).route_layer(permit(PollAction::ManageCollaborators)).route_layer(limited("polls.manage")))
+        .route("/:pollId/transfer", post(collaborators::transfer_ownership).route_layer(permit(PollAction::TransferOwnership)).route_layer(limited("polls.manage")))
+        .route("/:pollId/change/vote", post(change_vote::change_vote).route_layer(scoped(ApiScope::Vote)).route_layer(limited("polls.vote")))
+        .route("/user/polls", get(get_user_polls::get_polls_by_user).route_layer(scoped(ApiScope::ReadPolls)))
+        .route("/:pollId/vote/check", get(check_vote::check_user_vote).route_layer(scoped(ApiScope::ReadPolls)))
+        .layer(middleware::from_fn_with_state(state.clone(), crate::middleware::jwt::jwt_auth))
         .route("/:pollId/stream", get(get_results::poll_updates_stream))
         .route("/", get(polls::get_all_polls))
+        .layer(middleware::from_fn_with_state(state.clone(), crate::middleware::csrf::csrf_protect))
+        .route_layer(middleware::from_fn(crate::middleware::request_span::record_path_params))
         .with_state(state)
-}
\ No newline at end of file
+}
@@ -0,0 +1,11 @@
+use axum::{Router, routing::get};
+use crate::controllers::system_controllers::{health, metrics};
+use crate::state::AppState;
+
+pub fn system_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics::metrics))
+        .route("/healthz", get(health::healthz))
+        .route("/readyz", get(health::readyz))
+        .with_state(state)
+}
@@ -0,0 +1,9 @@
+use axum::{Router, routing::get};
+use crate::controllers::auth_controllers::jwks;
+use crate::state::AppState;
+
+pub fn well_known_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/jwks.json", get(jwks::jwks))
+        .with_state(state)
+}
@@ -2,14 +2,39 @@ use mongodb::Database;
 use std::sync::Arc;
 use webauthn_rs::prelude::Webauthn;
 
+use crate::config::Config;
+use crate::utils::{auth_guard::AuthGuard, background::BackgroundTasks, authenticator_policy::AuthenticatorPolicy, csrf::CsrfPolicy, jwt_keys::JwtKeys, metrics::Metrics, rate_limiter::RateLimiter, shutdown::Shutdown};
+
 #[derive(Clone)]
 pub struct AppState {
+    pub config: Arc<Config>,
     pub db: Arc<Database>,
     pub webauthn: Arc<Webauthn>,
+    pub jwt_keys: Arc<JwtKeys>,
+    pub authenticator_policy: Arc<AuthenticatorPolicy>,
+    pub auth_guard: Arc<AuthGuard>,
+    pub csrf: Arc<CsrfPolicy>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub metrics: Arc<Metrics>,
+    pub background: Arc<BackgroundTasks>,
+    pub shutdown: Arc<Shutdown>,
 }
 
 impl AppState {
-    pub fn new(db: Arc<Database>, webauthn: Arc<Webauthn>) -> Self {
-        Self { db, webauthn }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: Arc<Config>,
+        db: Arc<Database>,
+        webauthn: Arc<Webauthn>,
+        jwt_keys: Arc<JwtKeys>,
+        authenticator_policy: Arc<AuthenticatorPolicy>,
+        auth_guard: Arc<AuthGuard>,
+        csrf: Arc<CsrfPolicy>,
+        rate_limiter: Arc<RateLimiter>,
+        metrics: Arc<Metrics>,
+        background: Arc<BackgroundTasks>,
+        shutdown: Arc<Shutdown>,
+    ) -> Self {
+        Self { config, db, webauthn, jwt_keys, authenticator_policy, auth_guard, csrf, rate_limiter, metrics, background, shutdown }
     }
 }
\ No newline at end of file
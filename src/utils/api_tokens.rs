@@ -0,0 +1,139 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{bson::{doc, oid::ObjectId}, options::ReturnDocument, Database};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::models::{
+    api_token_models::{ApiScope, ApiToken},
+    user_models::User,
+};
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::utils::session::Claims;
+
+const TOKEN_PREFIX: &str = "pat_";
+const MAX_TOKENS_PER_USER: u64 = 20;
+
+#[derive(Debug, Clone)]
+pub struct ApiTokenAccess {
+    pub scopes: Vec<ApiScope>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScopeGranted;
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}{}", TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(TOKEN_PREFIX)
+}
+
+pub async fn create_api_token(
+    db: &Database,
+    user_id: ObjectId,
+    name: &str,
+    scopes: Vec<ApiScope>,
+    expires_in_days: Option<i64>,
+) -> AppResult<(ApiToken, String)> {
+    let tokens = db.collection::<ApiToken>("api_tokens");
+
+    let active = tokens
+        .count_documents(doc! { "user_id": user_id, "revoked_at": null })
+        .await?;
+
+    if active >= MAX_TOKENS_PER_USER {
+        return Err(AppError::Conflict(ErrorCode::ApiTokenLimitReached, format!("You can have at most {} active API tokens", MAX_TOKENS_PER_USER)));
+    }
+
+    let secret = generate_token();
+    let now = Utc::now();
+
+    let token = ApiToken {
+        id: ObjectId::new(),
+        user_id,
+        name: name.to_string(),
+        token_hash: hash_token(&secret),
+        prefix: secret.chars().take(TOKEN_PREFIX.len() + 8).collect(),
+        scopes,
+        created_at: now,
+        expires_at: expires_in_days.map(|days| now + Duration::days(days)),
+        last_used_at: None,
+        revoked_at: None,
+    };
+
+    tokens.insert_one(&token).await?;
+
+    Ok((token, secret))
+}
+
+pub async fn api_tokens_for_user(db: &Database, user_id: ObjectId) -> AppResult<Vec<ApiToken>> {
+    let tokens = db.collection::<ApiToken>("api_tokens")
+        .find(doc! { "user_id": user_id, "revoked_at": null })
+        .sort(doc! { "created_at": -1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(tokens)
+}
+
+pub async fn revoke_api_token(db: &Database, user_id: ObjectId, token_id: ObjectId) -> AppResult<ApiToken> {
+    db.collection::<ApiToken>("api_tokens")
+        .find_one_and_update(
+            doc! { "_id": token_id, "user_id": user_id, "revoked_at": null },
+            doc! { "$set": { "revoked_at": Utc::now().to_rfc3339() } },
+        )
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::ApiTokenNotFound, "API token not found".to_string()))
+}
+
+pub async fn revoke_user_api_tokens(db: &Database, user_id: ObjectId) -> AppResult<()> {
+    db.collection::<ApiToken>("api_tokens")
+        .update_many(
+            doc! { "user_id": user_id, "revoked_at": null },
+            doc! { "$set": { "revoked_at": Utc::now().to_rfc3339() } },
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn authenticate(db: &Database, presented: &str) -> AppResult<(Claims, ApiTokenAccess)> {
+    let token = db.collection::<ApiToken>("api_tokens")
+        .find_one_and_update(
+            doc! { "token_hash": hash_token(presented), "revoked_at": null },
+            doc! { "$set": { "last_used_at": Utc::now().to_rfc3339() } },
+        )
+        .return_document(ReturnDocument::After)
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::ApiTokenInvalid, "Invalid API token".to_string()))?;
+
+    if token.expires_at.is_some_and(|expires_at| expires_at < Utc::now()) {
+        return Err(AppError::AuthenticationError(ErrorCode::ApiTokenExpired, "API token has expired".to_string()));
+    }
+
+    let user = db.collection::<User>("users")
+        .find_one(doc! { "_id": token.user_id })
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::AccountNotFound, "API token owner no longer exists".to_string()))?;
+
+    let claims = Claims {
+        sub: user.id.to_hex(),
+        username: user.username,
+        display_name: user.display_name,
+        sid: token.id.to_hex(),
+        role: user.role,
+        exp: token.expires_at.map(|expires_at| expires_at.timestamp() as usize).unwrap_or(0),
+    };
+
+    Ok((claims, ApiTokenAccess { scopes: token.scopes }))
+}
@@ -0,0 +1,211 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{Duration, Utc};
+use mongodb::{
+    bson::{doc, DateTime as BsonDateTime, Document},
+    options::ReturnDocument,
+    Database,
+};
+use std::sync::Arc;
+use webauthn_rs::fake::{FakePasskeyDistribution, WebauthnFakeCredentialGenerator};
+use webauthn_rs::prelude::*;
+use webauthn_rs_proto::{AuthenticatorTransport, RegisteredExtensions, UserVerificationPolicy};
+
+use crate::config::AuthGuardConfig;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+
+pub struct AuthGuard {
+    decoy_credentials: WebauthnFakeCredentialGenerator<FakePasskeyDistribution>,
+    window_seconds: i64,
+    ip_limit: i64,
+    username_limit: i64,
+    lockout_threshold: i64,
+    lockout_seconds: i64,
+}
+
+pub fn init_auth_guard(config: &AuthGuardConfig) -> AppResult<Arc<AuthGuard>> {
+    let decoy_key = match &config.decoy_key {
+        Some(key) => STANDARD.decode(key)
+            .map_err(|e| AppError::InternalError(format!("AUTH_DECOY_KEY must be base64: {}", e)))?,
+        None => {
+            tracing::warn!("AUTH_DECOY_KEY not set, decoy credentials will change on every restart");
+            WebauthnFakeCredentialGenerator::<FakePasskeyDistribution>::new_hmac_key()
+                .map_err(|e| AppError::InternalError(format!("Failed to generate decoy key: {}", e)))?
+        }
+    };
+
+    let decoy_credentials = WebauthnFakeCredentialGenerator::new(&decoy_key)
+        .map_err(|e| AppError::InternalError(format!("Invalid AUTH_DECOY_KEY: {}", e)))?;
+
+    let guard = AuthGuard {
+        decoy_credentials,
+        window_seconds: config.rate_limit_window,
+        ip_limit: config.rate_limit_per_ip,
+        username_limit: config.rate_limit_per_username,
+        lockout_threshold: config.lockout_threshold,
+        lockout_seconds: config.lockout_seconds,
+    };
+
+    tracing::info!(
+        ip_limit = guard.ip_limit,
+        username_limit = guard.username_limit,
+        window_seconds = guard.window_seconds,
+        lockout_threshold = guard.lockout_threshold,
+        lockout_seconds = guard.lockout_seconds,
+        "Auth guard loaded"
+    );
+
+    Ok(Arc::new(guard))
+}
+
+impl AuthGuard {
+    pub fn decoy_passkeys(&self, username: &str) -> AppResult<Vec<Passkey>> {
+        let credential_ids = self.decoy_credentials
+            .generate(username.as_bytes())
+            .map_err(|e| AppError::InternalError(format!("Failed to generate decoy credentials: {}", e)))?;
+
+        Ok(credential_ids
+            .into_iter()
+            .map(|cred_id| {
+                Passkey::from(Credential {
+                    cred_id,
+                    cred: COSEKey {
+                        type_: COSEAlgorithm::ES256,
+                        key: COSEKeyType::EC_EC2(COSEEC2Key {
+                            curve: ECDSACurve::SECP256R1,
+                            x: vec![0; 32].into(),
+                            y: vec![0; 32].into(),
+                        }),
+                    },
+                    counter: 0,
+                    transports: Some(vec![AuthenticatorTransport::Internal, AuthenticatorTransport::Hybrid]),
+                    user_verified: true,
+                    backup_eligible: true,
+                    backup_state: true,
+                    registration_policy: UserVerificationPolicy::Required,
+                    extensions: RegisteredExtensions::default(),
+                    attestation: ParsedAttestation::default(),
+                    attestation_format: AttestationFormat::None,
+                })
+            })
+            .collect())
+    }
+
+    async fn hit(&self, db: &Database, key: &str, limit: i64, message: &str) -> AppResult<()> {
+        let now = Utc::now().timestamp();
+        let window_start = now - now.rem_euclid(self.window_seconds);
+        let rate_limits = db.collection::<Document>("auth_rate_limits");
+
+        let counted = rate_limits
+            .find_one_and_update(
+                doc! { "_id": key, "window_start": window_start },
+                doc! { "$inc": { "count": 1 } },
+            )
+            .return_document(ReturnDocument::After)
+            .await?;
+
+        let count = match counted {
+            Some(record) => record.get_i64("count").unwrap_or(i64::MAX),
+            None => {
+                rate_limits
+                    .update_one(
+                        doc! { "_id": key },
+                        doc! { "$set": { "window_start": window_start, "count": 1_i64 } },
+                    )
+                    .upsert(true)
+                    .await?;
+                1
+            }
+        };
+
+        if count > limit {
+            return Err(AppError::RateLimited {
+                code: ErrorCode::TooManyRequests,
+                message: message.to_string(),
+                retry_after: window_start + self.window_seconds - now,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub async fn purge_expired_windows(&self, db: &Database) -> AppResult<()> {
+        let now = Utc::now().timestamp();
+        let current_window = now - now.rem_euclid(self.window_seconds);
+
+        let result = db.collection::<Document>("auth_rate_limits")
+            .delete_many(doc! { "window_start": { "$lt": current_window } })
+            .await?;
+
+        if result.deleted_count > 0 {
+            tracing::debug!(deleted = result.deleted_count, "Purged expired auth rate limit windows");
+        }
+
+        Ok(())
+    }
+
+    pub async fn check_ip(&self, db: &Database, ip: &str) -> AppResult<()> {
+        self.hit(db, &format!("ip:{}", ip), self.ip_limit, "Too many requests from this address").await
+    }
+
+    pub async fn check_username(&self, db: &Database, username: &str) -> AppResult<()> {
+        self.hit(db, &format!("username:{}", username), self.username_limit, "Too many attempts for this account").await
+    }
+
+    pub async fn ensure_not_locked(&self, db: &Database, username: &str) -> AppResult<()> {
+        let lockout = db.collection::<Document>("auth_lockouts")
+            .find_one(doc! { "_id": username, "locked_until": { "$gt": BsonDateTime::now() } })
+            .await?;
+
+        if let Some(lockout) = lockout
+            && let Ok(locked_until) = lockout.get_datetime("locked_until")
+        {
+            let remaining = (locked_until.to_chrono() - Utc::now()).num_seconds().max(1);
+            return Err(AppError::RateLimited {
+                code: ErrorCode::AccountLocked,
+                message: "Too many failed sign-in attempts, try again later".to_string(),
+                retry_after: remaining,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub async fn record_failed_verification(&self, db: &Database, username: &str) -> AppResult<()> {
+        let lockouts = db.collection::<Document>("auth_lockouts");
+
+        let record = lockouts
+            .find_one_and_update(
+                doc! { "_id": username },
+                doc! {
+                    "$inc": { "failures": 1_i64 },
+                    "$set": { "last_failure_at": BsonDateTime::now() },
+                },
+            )
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await?;
+
+        let failures = record.and_then(|record| record.get_i64("failures").ok()).unwrap_or(0);
+
+        if failures >= self.lockout_threshold {
+            let locked_until = Utc::now() + Duration::seconds(self.lockout_seconds);
+            tracing::warn!(username = %username, %locked_until, failures, "Locking sign-in after repeated failures");
+            lockouts
+                .update_one(
+                    doc! { "_id": username },
+                    doc! { "$set": { "failures": 0_i64, "locked_until": BsonDateTime::from_chrono(locked_until) } },
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn clear_failures(&self, db: &Database, username: &str) -> AppResult<()> {
+        db.collection::<Document>("auth_lockouts")
+            .delete_one(doc! { "_id": username })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+use base64::{engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD}, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::{BTreeMap, HashMap, HashSet}, fs, sync::Arc};
+use webauthn_rs::prelude::*;
+
+use crate::config::WebauthnConfig;
+use crate::models::user_models::Role;
+use crate::state::AppState;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::utils::metrics;
+
+#[derive(Debug, Clone)]
+struct MetadataEntry {
+    description: String,
+    key_protection: Vec<String>,
+    attestation_types: Vec<String>,
+    root_certificates: Vec<String>,
+}
+
+impl MetadataEntry {
+    fn is_software(&self) -> bool {
+        self.key_protection.iter().any(|kp| kp == "software")
+            || self.attestation_types.iter().any(|at| at == "basic_surrogate")
+    }
+}
+
+pub struct AuthenticatorPolicy {
+    require_user_verification: bool,
+    block_cloned_credentials: bool,
+    aaguid_allow_list: Option<HashSet<Uuid>>,
+    metadata: HashMap<Uuid, MetadataEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "ceremony", content = "state")]
+pub enum PendingRegistration {
+    Passkey(PasskeyRegistration),
+    Attested(AttestedPasskeyRegistration),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    pub decision: String,
+    pub attested: bool,
+    pub aaguid: Option<String>,
+    pub authenticator: Option<String>,
+    pub user_verified: bool,
+    pub admin_account: bool,
+    pub checks: Vec<String>,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataBlob {
+    entries: Vec<MetadataBlobEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataBlobEntry {
+    aaguid: Option<Uuid>,
+    metadata_statement: Option<MetadataStatement>,
+    #[serde(default)]
+    status_reports: Vec<StatusReport>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataStatement {
+    description: String,
+    #[serde(default)]
+    key_protection: Vec<String>,
+    #[serde(default)]
+    attestation_types: Vec<String>,
+    #[serde(default)]
+    attestation_root_certificates: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct StatusReport {
+    status: String,
+}
+
+fn load_metadata(path: &str) -> AppResult<HashMap<Uuid, MetadataEntry>> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| AppError::InternalError(format!("Failed to read FIDO metadata file '{}': {}", path, e)))?;
+    let raw = raw.trim();
+
+    // The MDS3 download is a JWT; a pre-decoded JSON payload is accepted as well.
+    let payload = if raw.starts_with('{') {
+        raw.as_bytes().to_vec()
+    } else {
+        let encoded = raw.split('.').nth(1)
+            .ok_or_else(|| AppError::InternalError("FIDO metadata file is neither JSON nor a JWT".to_string()))?;
+        URL_SAFE_NO_PAD.decode(encoded.trim_end_matches('='))
+            .map_err(|e| AppError::InternalError(format!("Invalid FIDO metadata JWT payload: {}", e)))?
+    };
+
+    let blob: MetadataBlob = serde_json::from_slice(&payload)?;
+
+    let mut metadata = HashMap::new();
+    for entry in blob.entries {
+        let (Some(aaguid), Some(statement)) = (entry.aaguid, entry.metadata_statement) else {
+            continue;
+        };
+
+        let compromised = entry.status_reports.iter()
+            .any(|report| report.status == "REVOKED" || report.status.contains("COMPROMISE"));
+        if compromised {
+            continue;
+        }
+
+        metadata.insert(aaguid, MetadataEntry {
+            description: statement.description,
+            key_protection: statement.key_protection,
+            attestation_types: statement.attestation_types,
+            root_certificates: statement.attestation_root_certificates,
+        });
+    }
+
+    Ok(metadata)
+}
+
+pub fn init_authenticator_policy(config: &WebauthnConfig) -> AppResult<Arc<AuthenticatorPolicy>> {
+    let require_user_verification = config.require_user_verification;
+    let block_cloned_credentials = config.block_cloned_credentials;
+
+    let aaguid_allow_list = if config.aaguid_allow_list.is_empty() {
+        None
+    } else {
+        let parsed = config.aaguid_allow_list.iter()
+            .map(|aaguid| Uuid::parse_str(aaguid)
+                .map_err(|e| AppError::InternalError(format!("Invalid AAGUID '{}' in WEBAUTHN_AAGUID_ALLOWLIST: {}", aaguid, e))))
+            .collect::<AppResult<HashSet<Uuid>>>()?;
+        Some(parsed)
+    };
+
+    let metadata = match &config.metadata_path {
+        Some(path) => load_metadata(path)?,
+        None => {
+            tracing::warn!("WEBAUTHN_METADATA_PATH not set, admin accounts will not be able to register passkeys");
+            HashMap::new()
+        }
+    };
+
+    if let Some(allow_list) = &aaguid_allow_list {
+        for aaguid in allow_list {
+            if !metadata.contains_key(aaguid) {
+                return Err(AppError::InternalError(format!("AAGUID {} is not present in the FIDO metadata file", aaguid)));
+            }
+        }
+    }
+
+    tracing::info!(
+        require_user_verification,
+        cloned_credentials = if block_cloned_credentials { "block" } else { "warn" },
+        allow_listed_aaguids = %aaguid_allow_list.as_ref().map(|list| list.len().to_string()).unwrap_or_else(|| "any".to_string()),
+        metadata_entries = metadata.len(),
+        "Authenticator policy loaded"
+    );
+
+    Ok(Arc::new(AuthenticatorPolicy {
+        require_user_verification,
+        block_cloned_credentials,
+        aaguid_allow_list,
+        metadata,
+    }))
+}
+
+impl AuthenticatorPolicy {
+    pub fn requires_user_verification(&self) -> bool {
+        self.require_user_verification
+    }
+
+    pub fn blocks_cloned_credentials(&self) -> bool {
+        self.block_cloned_credentials
+    }
+
+    fn requires_attestation(&self, role: Role) -> bool {
+        self.aaguid_allow_list.is_some() || role == Role::Admin
+    }
+
+    /// Whether a passkey registered under an earlier decision is strong enough for `role`.
+    /// Used when an existing account is promoted, since its passkeys were never held to the admin rule.
+    pub fn satisfies_role(&self, role: Role, decision: &PolicyDecision) -> bool {
+        if role != Role::Admin {
+            return true;
+        }
+
+        let entry = decision.aaguid
+            .as_deref()
+            .and_then(|aaguid| Uuid::parse_str(aaguid).ok())
+            .and_then(|aaguid| self.metadata.get(&aaguid));
+
+        decision.decision == "allowed" && decision.attested && entry.is_some_and(|entry| !entry.is_software())
+    }
+
+    fn attestation_ca_list(&self, role: Role) -> AppResult<AttestationCaList> {
+        let admin = role == Role::Admin;
+        let mut builder = AttestationCaListBuilder::new();
+
+        for (aaguid, entry) in &self.metadata {
+            if let Some(allow_list) = &self.aaguid_allow_list
+                && !allow_list.contains(aaguid)
+            {
+                continue;
+            }
+            if admin && entry.is_software() {
+                continue;
+            }
+
+            for certificate in &entry.root_certificates {
+                let der = STANDARD.decode(certificate)
+                    .map_err(|e| AppError::InternalError(format!("Invalid root certificate for {}: {}", aaguid, e)))?;
+                builder
+                    .insert_device_der(&der, *aaguid, entry.description.clone(), BTreeMap::new())
+                    .map_err(|e| AppError::InternalError(format!("Invalid root certificate for {}: {}", aaguid, e)))?;
+            }
+        }
+
+        let ca_list = builder.build();
+        if ca_list.is_empty() {
+            return Err(AppError::BadRequest(
+                ErrorCode::NoTrustedAuthenticators,
+                "No trusted authenticators are configured for this account type".to_string(),
+            ));
+        }
+
+        Ok(ca_list)
+    }
+
+    fn evaluate(&self, role: Role, credential: &Credential) -> PolicyDecision {
+        let admin_account = role == Role::Admin;
+        let aaguid = match &credential.attestation.metadata {
+            AttestationMetadata::Packed { aaguid } | AttestationMetadata::Tpm { aaguid, .. } => Some(*aaguid),
+            _ => None,
+        };
+        let attested = !matches!(
+            credential.attestation.data,
+            ParsedAttestationData::None | ParsedAttestationData::Self_ | ParsedAttestationData::Uncertain
+        );
+        let entry = aaguid.and_then(|aaguid| self.metadata.get(&aaguid));
+
+        let mut violations = Vec::new();
+        let mut checks = Vec::new();
+
+        if self.require_user_verification {
+            if credential.user_verified {
+                checks.push("user_verification".to_string());
+            } else {
+                violations.push("user verification is required".to_string());
+            }
+        }
+
+        if let Some(allow_list) = &self.aaguid_allow_list {
+            match aaguid {
+                Some(aaguid) if attested && allow_list.contains(&aaguid) => checks.push("aaguid_allow_list".to_string()),
+                _ => violations.push("authenticator model is not on the allow-list".to_string()),
+            }
+        }
+
+        if admin_account {
+            match entry {
+                Some(entry) if attested && !entry.is_software() => checks.push("hardware_authenticator".to_string()),
+                _ => violations.push("admin accounts require an attested hardware authenticator".to_string()),
+            }
+        }
+
+        PolicyDecision {
+            decision: if violations.is_empty() { "allowed".to_string() } else { "rejected".to_string() },
+            attested,
+            aaguid: aaguid.map(|aaguid| aaguid.to_string()),
+            authenticator: entry.map(|entry| entry.description.clone()),
+            user_verified: credential.user_verified,
+            admin_account,
+            checks: if violations.is_empty() { checks } else { violations },
+            evaluated_at: Utc::now(),
+        }
+    }
+}
+
+pub fn start_registration(
+    state: &AppState,
+    user_unique_id: Uuid,
+    username: &str,
+    display_name: &str,
+    role: Role,
+    exclude_credentials: Option<Vec<CredentialID>>,
+) -> AppResult<(CreationChallengeResponse, PendingRegistration)> {
+    let policy = &state.authenticator_policy;
+
+    if policy.requires_attestation(role) {
+        let ca_list = policy.attestation_ca_list(role)?;
+        let (ccr, reg_state) = state.webauthn
+            .start_attested_passkey_registration(user_unique_id, username, display_name, exclude_credentials, ca_list, None)
+            .map_err(|e| AppError::WebauthnError(format!("Failed to start passkey registration: {}", e)))?;
+        Ok((ccr, PendingRegistration::Attested(reg_state)))
+    } else {
+        let (ccr, reg_state) = state.webauthn
+            .start_passkey_registration(user_unique_id, username, display_name, exclude_credentials)
+            .map_err(|e| AppError::WebauthnError(format!("Failed to start passkey registration: {}", e)))?;
+        Ok((ccr, PendingRegistration::Passkey(reg_state)))
+    }
+}
+
+pub fn finish_registration(
+    state: &AppState,
+    username: &str,
+    role: Role,
+    credential: &RegisterPublicKeyCredential,
+    pending: &PendingRegistration,
+) -> AppResult<(Passkey, PolicyDecision)> {
+    let result = verify_registration(state, username, role, credential, pending);
+    state.metrics.record_webauthn(metrics::REGISTRATION, &result);
+    result
+}
+
+fn verify_registration(
+    state: &AppState,
+    username: &str,
+    role: Role,
+    credential: &RegisterPublicKeyCredential,
+    pending: &PendingRegistration,
+) -> AppResult<(Passkey, PolicyDecision)> {
+    let registered: Credential = match pending {
+        PendingRegistration::Passkey(reg_state) => state.webauthn
+            .finish_passkey_registration(credential, reg_state)
+            .map_err(|e| AppError::WebauthnError(format!("Passkey registration failed: {}", e)))?
+            .into(),
+        PendingRegistration::Attested(reg_state) => state.webauthn
+            .finish_attested_passkey_registration(credential, reg_state)
+            .map_err(|e| AppError::WebauthnError(format!("Passkey registration failed: {}", e)))?
+            .into(),
+    };
+
+    let decision = state.authenticator_policy.evaluate(role, &registered);
+
+    if decision.decision != "allowed" {
+        tracing::warn!(username = %username, checks = %decision.checks.join(", "), "Passkey rejected by authenticator policy");
+        return Err(AppError::BadRequest(ErrorCode::AuthenticatorRejected, format!(
+            "Authenticator rejected by policy: {}",
+            decision.checks.join(", ")
+        )));
+    }
+
+    Ok((Passkey::from(registered), decision))
+}
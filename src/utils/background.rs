@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use std::{future::Future, sync::{Arc, Mutex}, time::Duration};
+use tokio::{sync::watch, task::JoinHandle, time::MissedTickBehavior};
+
+use crate::utils::error::AppResult;
+use crate::utils::shutdown::Shutdown;
+
+const STALE_AFTER_INTERVALS: u32 = 3;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaskHealth {
+    pub name: &'static str,
+    pub healthy: bool,
+    pub running: bool,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Default)]
+struct TaskStatus {
+    last_success_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    consecutive_failures: u32,
+}
+
+struct BackgroundTask {
+    name: &'static str,
+    interval: Duration,
+    started_at: DateTime<Utc>,
+    status: Arc<Mutex<TaskStatus>>,
+    handle: JoinHandle<()>,
+}
+
+pub struct BackgroundTasks {
+    shutdown: watch::Receiver<bool>,
+    tasks: Mutex<Vec<BackgroundTask>>,
+}
+
+pub fn init_background_tasks(shutdown: &Shutdown) -> Arc<BackgroundTasks> {
+    Arc::new(BackgroundTasks {
+        shutdown: shutdown.subscribe(),
+        tasks: Mutex::new(Vec::new()),
+    })
+}
+
+impl BackgroundTasks {
+    pub fn spawn<F, Fut>(&self, name: &'static str, interval: Duration, job: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = AppResult<()>> + Send,
+    {
+        let status = Arc::new(Mutex::new(TaskStatus::default()));
+        let task_status = Arc::clone(&status);
+        let mut shutdown = self.shutdown.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown.wait_for(|stopping| *stopping) => break,
+                }
+
+                let result = job().await;
+                let mut status = task_status.lock().unwrap_or_else(|e| e.into_inner());
+                match result {
+                    Ok(()) => {
+                        status.last_success_at = Some(Utc::now());
+                        status.last_error = None;
+                        status.consecutive_failures = 0;
+                    }
+                    Err(e) => {
+                        tracing::warn!(task = name, error = %e, "Background task failed");
+                        status.last_error = Some(e.to_string());
+                        status.consecutive_failures += 1;
+                    }
+                }
+            }
+        });
+
+        tracing::info!(task = name, interval_seconds = interval.as_secs(), "Background task started");
+
+        self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(BackgroundTask { name, interval, started_at: Utc::now(), status, handle });
+    }
+
+    pub fn health(&self) -> Vec<TaskHealth> {
+        let now = Utc::now();
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+
+        tasks
+            .iter()
+            .map(|task| {
+                let status = task.status.lock().unwrap_or_else(|e| e.into_inner());
+                let running = !task.handle.is_finished();
+                let stale_after = ChronoDuration::from_std(task.interval * STALE_AFTER_INTERVALS).unwrap_or(ChronoDuration::MAX);
+                let last_progress = status.last_success_at.unwrap_or(task.started_at);
+
+                TaskHealth {
+                    name: task.name,
+                    healthy: running && now - last_progress <= stale_after,
+                    running,
+                    last_success_at: status.last_success_at,
+                    last_error: status.last_error.clone(),
+                    consecutive_failures: status.consecutive_failures,
+                }
+            })
+            .collect()
+    }
+
+    pub async fn join(&self, timeout: Duration) {
+        let handles: Vec<(&'static str, JoinHandle<()>)> = self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain(..)
+            .map(|task| (task.name, task.handle))
+            .collect();
+
+        for (name, handle) in handles {
+            let abort = handle.abort_handle();
+            match tokio::time::timeout(timeout, handle).await {
+                Ok(_) => tracing::info!(task = name, "Background task stopped"),
+                Err(_) => {
+                    tracing::warn!(task = name, "Background task did not stop in time, aborting");
+                    abort.abort();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use mongodb::bson::DateTime as BsonDateTime;
+use std::time::Duration;
+
+pub const CHALLENGE_TTL: Duration = Duration::from_secs(300);
+
+pub fn issued_after() -> BsonDateTime {
+    BsonDateTime::from_millis(BsonDateTime::now().timestamp_millis() - CHALLENGE_TTL.as_millis() as i64)
+}
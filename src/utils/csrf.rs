@@ -0,0 +1,56 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use std::sync::Arc;
+
+use crate::config::ServerConfig;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::origins::{self, OriginPattern};
+
+pub const CSRF_COOKIE: &str = "csrf_token";
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+pub struct CsrfPolicy {
+    trusted_origins: Vec<OriginPattern>,
+}
+
+impl CsrfPolicy {
+    pub fn is_trusted_origin(&self, value: &str) -> bool {
+        origins::any_matches(&self.trusted_origins, value)
+    }
+}
+
+pub fn generate_csrf_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn tokens_match(expected: &str, presented: &str) -> bool {
+    if expected.is_empty() || expected.len() != presented.len() {
+        return false;
+    }
+
+    expected
+        .bytes()
+        .zip(presented.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+pub fn init_csrf_policy(config: &ServerConfig) -> AppResult<Arc<CsrfPolicy>> {
+    let mut trusted_origins = Vec::new();
+
+    for origin in config.cors_origins.iter().chain(&config.csrf_trusted_origins) {
+        if !trusted_origins.contains(origin) {
+            trusted_origins.push(origin.clone());
+        }
+    }
+
+    if trusted_origins.is_empty() {
+        return Err(AppError::InternalError("CSRF protection needs at least one trusted origin".to_string()));
+    }
+
+    tracing::info!(trusted_origins = trusted_origins.len(), "CSRF protection enabled");
+
+    Ok(Arc::new(CsrfPolicy { trusted_origins }))
+}
@@ -1,13 +1,221 @@
 use axum::{
-    http::StatusCode,
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::fmt;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::middleware::{api_version::ApiVersion, request_span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Generic, one per AppError variant
+    DatabaseError,
+    ValidationError,
+    InternalError,
+    WebauthnError,
+    SerializationError,
+    StepUpRequired,
+
+    // Field-level validation
+    Required,
+    TooShort,
+    TooLong,
+    TooFew,
+    TooMany,
+    OutOfRange,
+    InvalidCharacters,
+    Duplicate,
+    InvalidId,
+
+    // Authentication
+    NotAuthenticated,
+    InvalidToken,
+    InvalidCredentials,
+    SessionRevoked,
+    RefreshTokenInvalid,
+    RefreshTokenExpired,
+    RefreshTokenReused,
+    ApiTokenInvalid,
+    ApiTokenExpired,
+    AccountNotFound,
+    PasskeyMismatch,
+    PasskeyVerificationFailed,
+    PasskeyDisabled,
+    UserVerificationRequired,
+
+    // Authorization
+    CsrfFailed,
+    UntrustedOrigin,
+    ApiTokenNotAllowed,
+    InsufficientScope,
+    InsufficientRole,
+    PollPermissionDenied,
+    OrganizationPermissionDenied,
+
+    // Missing resources
+    PollNotFound,
+    UserNotFound,
+    OrganizationNotFound,
+    MemberNotFound,
+    InvitationNotFound,
+    PasskeyNotFound,
+    ApiTokenNotFound,
+    CollaboratorNotFound,
+    ChallengeNotFound,
+
+    // Conflicts
+    UsernameTaken,
+    AlreadyVoted,
+    VoteUnchanged,
+    OptionsLocked,
+    LastOwner,
+    LastPasskey,
+    SelfModification,
+    AlreadyMember,
+    InvitationPending,
+    CollaboratorIsOwner,
+    OwnershipChanged,
+    AdminAuthenticatorRequired,
+    UserOwnsResources,
+    PasskeyAlreadyRegistered,
+    ApiTokenLimitReached,
+
+    // Rejected requests
+    MalformedBody,
+    PollClosed,
+    InvalidOption,
+    NotVoted,
+    InvitationExpired,
+    InvalidCredential,
+    AuthenticatorRejected,
+    NoTrustedAuthenticators,
+
+    // Throttling
+    TooManyRequests,
+    AccountLocked,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+            ErrorCode::ValidationError => "VALIDATION_ERROR",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::WebauthnError => "WEBAUTHN_ERROR",
+            ErrorCode::SerializationError => "SERIALIZATION_ERROR",
+            ErrorCode::StepUpRequired => "STEP_UP_REQUIRED",
+            ErrorCode::Required => "REQUIRED",
+            ErrorCode::TooShort => "TOO_SHORT",
+            ErrorCode::TooLong => "TOO_LONG",
+            ErrorCode::TooFew => "TOO_FEW",
+            ErrorCode::TooMany => "TOO_MANY",
+            ErrorCode::OutOfRange => "OUT_OF_RANGE",
+            ErrorCode::InvalidCharacters => "INVALID_CHARACTERS",
+            ErrorCode::Duplicate => "DUPLICATE",
+            ErrorCode::InvalidId => "INVALID_ID",
+            ErrorCode::NotAuthenticated => "NOT_AUTHENTICATED",
+            ErrorCode::InvalidToken => "INVALID_TOKEN",
+            ErrorCode::InvalidCredentials => "INVALID_CREDENTIALS",
+            ErrorCode::SessionRevoked => "SESSION_REVOKED",
+            ErrorCode::RefreshTokenInvalid => "REFRESH_TOKEN_INVALID",
+            ErrorCode::RefreshTokenExpired => "REFRESH_TOKEN_EXPIRED",
+            ErrorCode::RefreshTokenReused => "REFRESH_TOKEN_REUSED",
+            ErrorCode::ApiTokenInvalid => "API_TOKEN_INVALID",
+            ErrorCode::ApiTokenExpired => "API_TOKEN_EXPIRED",
+            ErrorCode::AccountNotFound => "ACCOUNT_NOT_FOUND",
+            ErrorCode::PasskeyMismatch => "PASSKEY_MISMATCH",
+            ErrorCode::PasskeyVerificationFailed => "PASSKEY_VERIFICATION_FAILED",
+            ErrorCode::PasskeyDisabled => "PASSKEY_DISABLED",
+            ErrorCode::UserVerificationRequired => "USER_VERIFICATION_REQUIRED",
+            ErrorCode::CsrfFailed => "CSRF_FAILED",
+            ErrorCode::UntrustedOrigin => "UNTRUSTED_ORIGIN",
+            ErrorCode::ApiTokenNotAllowed => "API_TOKEN_NOT_ALLOWED",
+            ErrorCode::InsufficientScope => "INSUFFICIENT_SCOPE",
+            ErrorCode::InsufficientRole => "INSUFFICIENT_ROLE",
+            ErrorCode::PollPermissionDenied => "POLL_PERMISSION_DENIED",
+            ErrorCode::OrganizationPermissionDenied => "ORGANIZATION_PERMISSION_DENIED",
+            ErrorCode::PollNotFound => "POLL_NOT_FOUND",
+            ErrorCode::UserNotFound => "USER_NOT_FOUND",
+            ErrorCode::OrganizationNotFound => "ORGANIZATION_NOT_FOUND",
+            ErrorCode::MemberNotFound => "MEMBER_NOT_FOUND",
+            ErrorCode::InvitationNotFound => "INVITATION_NOT_FOUND",
+            ErrorCode::PasskeyNotFound => "PASSKEY_NOT_FOUND",
+            ErrorCode::ApiTokenNotFound => "API_TOKEN_NOT_FOUND",
+            ErrorCode::CollaboratorNotFound => "COLLABORATOR_NOT_FOUND",
+            ErrorCode::ChallengeNotFound => "CHALLENGE_NOT_FOUND",
+            ErrorCode::UsernameTaken => "USERNAME_TAKEN",
+            ErrorCode::AlreadyVoted => "ALREADY_VOTED",
+            ErrorCode::VoteUnchanged => "VOTE_UNCHANGED",
+            ErrorCode::OptionsLocked => "OPTIONS_LOCKED",
+            ErrorCode::LastOwner => "LAST_OWNER",
+            ErrorCode::LastPasskey => "LAST_PASSKEY",
+            ErrorCode::SelfModification => "SELF_MODIFICATION",
+            ErrorCode::AlreadyMember => "ALREADY_MEMBER",
+            ErrorCode::InvitationPending => "INVITATION_PENDING",
+            ErrorCode::CollaboratorIsOwner => "COLLABORATOR_IS_OWNER",
+            ErrorCode::OwnershipChanged => "OWNERSHIP_CHANGED",
+            ErrorCode::AdminAuthenticatorRequired => "ADMIN_AUTHENTICATOR_REQUIRED",
+            ErrorCode::UserOwnsResources => "USER_OWNS_RESOURCES",
+            ErrorCode::PasskeyAlreadyRegistered => "PASSKEY_ALREADY_REGISTERED",
+            ErrorCode::ApiTokenLimitReached => "API_TOKEN_LIMIT_REACHED",
+            ErrorCode::MalformedBody => "MALFORMED_BODY",
+            ErrorCode::PollClosed => "POLL_CLOSED",
+            ErrorCode::InvalidOption => "INVALID_OPTION",
+            ErrorCode::NotVoted => "NOT_VOTED",
+            ErrorCode::InvitationExpired => "INVITATION_EXPIRED",
+            ErrorCode::InvalidCredential => "INVALID_CREDENTIAL",
+            ErrorCode::AuthenticatorRejected => "AUTHENTICATOR_REJECTED",
+            ErrorCode::NoTrustedAuthenticators => "NO_TRUSTED_AUTHENTICATORS",
+            ErrorCode::TooManyRequests => "TOO_MANY_REQUESTS",
+            ErrorCode::AccountLocked => "ACCOUNT_LOCKED",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: ErrorCode, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.into(),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
+    pub error: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Error body served under `/api/v1`: a generic error kind and an optional free-text detail.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LegacyErrorResponse {
     pub error: String,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -17,102 +225,185 @@ pub struct ErrorResponse {
 #[derive(Debug)]
 pub enum AppError {
     DatabaseError(String),
-    ValidationError(String),
-    AuthenticationError(String),
-    NotFound(String),
-    Conflict(String),
-    BadRequest(String),
+    ValidationError(Vec<FieldError>),
+    AuthenticationError(ErrorCode, String),
+    Forbidden(ErrorCode, String),
+    NotFound(ErrorCode, String),
+    Conflict(ErrorCode, String),
+    BadRequest(ErrorCode, String),
     InternalError(String),
     WebauthnError(String),
     SerializationError(String),
+    StepUpRequired { max_age: i64 },
+    RateLimited { code: ErrorCode, message: String, retry_after: i64 },
+}
+
+impl AppError {
+    pub fn invalid(field: impl Into<String>, code: ErrorCode, message: impl Into<String>) -> Self {
+        AppError::ValidationError(vec![FieldError::new(field, code, message)])
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::DatabaseError(_) => ErrorCode::DatabaseError,
+            AppError::ValidationError(_) => ErrorCode::ValidationError,
+            AppError::AuthenticationError(code, _)
+            | AppError::Forbidden(code, _)
+            | AppError::NotFound(code, _)
+            | AppError::Conflict(code, _)
+            | AppError::BadRequest(code, _)
+            | AppError::RateLimited { code, .. } => *code,
+            AppError::InternalError(_) => ErrorCode::InternalError,
+            AppError::WebauthnError(_) => ErrorCode::WebauthnError,
+            AppError::SerializationError(_) => ErrorCode::SerializationError,
+            AppError::StepUpRequired { .. } => ErrorCode::StepUpRequired,
+        }
+    }
+
+    fn legacy_kind(&self) -> &'static str {
+        match self {
+            AppError::DatabaseError(_) => "DATABASE_ERROR",
+            AppError::ValidationError(_) => "VALIDATION_ERROR",
+            AppError::AuthenticationError(..) => "AUTHENTICATION_ERROR",
+            AppError::Forbidden(..) => "FORBIDDEN",
+            AppError::NotFound(..) => "NOT_FOUND",
+            AppError::Conflict(..) => "CONFLICT",
+            AppError::BadRequest(..) => "BAD_REQUEST",
+            AppError::InternalError(_) => "INTERNAL_ERROR",
+            AppError::WebauthnError(_) => "WEBAUTHN_ERROR",
+            AppError::SerializationError(_) => "SERIALIZATION_ERROR",
+            AppError::StepUpRequired { .. } => "STEP_UP_REQUIRED",
+            AppError::RateLimited { .. } => "RATE_LIMITED",
+        }
+    }
+}
+
+fn describe_fields(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
-            AppError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
-            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
-            AppError::Conflict(msg) => write!(f, "Conflict: {}", msg),
-            AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::ValidationError(errors) => write!(f, "Validation error: {}", describe_fields(errors)),
+            AppError::AuthenticationError(code, msg) => write!(f, "Authentication error ({}): {}", code, msg),
+            AppError::Forbidden(code, msg) => write!(f, "Forbidden ({}): {}", code, msg),
+            AppError::NotFound(code, msg) => write!(f, "Not found ({}): {}", code, msg),
+            AppError::Conflict(code, msg) => write!(f, "Conflict ({}): {}", code, msg),
+            AppError::BadRequest(code, msg) => write!(f, "Bad request ({}): {}", code, msg),
             AppError::InternalError(msg) => write!(f, "Internal error: {}", msg),
             AppError::WebauthnError(msg) => write!(f, "Webauthn error: {}", msg),
             AppError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
+            AppError::StepUpRequired { max_age } => write!(f, "Step-up required within {}s", max_age),
+            AppError::RateLimited { code, message, retry_after } => {
+                write!(f, "Rate limited ({}): {} (retry after {}s)", code, message, retry_after)
+            }
         }
     }
 }
 
 impl std::error::Error for AppError {}
 
+impl AppError {
+    fn log(&self) {
+        let kind = self.code().as_str();
+        match self {
+            AppError::DatabaseError(msg) => tracing::error!(kind, error = %msg, "Database operation failed"),
+            AppError::InternalError(msg) => tracing::error!(kind, error = %msg, "Internal error"),
+            AppError::SerializationError(msg) => tracing::error!(kind, error = %msg, "Serialization failed"),
+            AppError::WebauthnError(msg) => tracing::warn!(kind, error = %msg, "Passkey operation failed"),
+            AppError::AuthenticationError(_, msg) => tracing::info!(kind, error = %msg, "Request not authenticated"),
+            AppError::Forbidden(_, msg) => tracing::info!(kind, error = %msg, "Request forbidden"),
+            AppError::RateLimited { message, retry_after, .. } => {
+                tracing::warn!(kind, error = %message, retry_after, "Request rate limited")
+            }
+            other => tracing::debug!(kind, error = %other, "Request rejected"),
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, error_type, message) = match self {
-            AppError::DatabaseError(msg) => {
+        self.log();
+
+        let error = self.code();
+        let legacy_kind = self.legacy_kind();
+        let mut details = Vec::new();
+        let mut hint = None;
+        let mut retry_after = None;
+        let (status, message) = match self {
+            AppError::DatabaseError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database operation failed".to_string(),
+            ),
+            AppError::ValidationError(errors) => {
+                details = errors;
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "DATABASE_ERROR",
-                    "Database operation failed".to_string(),
+                    StatusCode::BAD_REQUEST,
+                    "Request validation failed".to_string(),
                 )
             }
-            AppError::ValidationError(msg) => (
-                StatusCode::BAD_REQUEST,
-                "VALIDATION_ERROR",
-                msg,
-            ),
-            AppError::AuthenticationError(msg) => (
-                StatusCode::UNAUTHORIZED,
-                "AUTHENTICATION_ERROR",
-                msg,
-            ),
-            AppError::NotFound(msg) => (
-                StatusCode::NOT_FOUND,
-                "NOT_FOUND",
-                msg,
-            ),
-            AppError::Conflict(msg) => (
-                StatusCode::CONFLICT,
-                "CONFLICT",
-                msg,
+            AppError::AuthenticationError(_, msg) => (StatusCode::UNAUTHORIZED, msg),
+            AppError::Forbidden(_, msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::NotFound(_, msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::Conflict(_, msg) => (StatusCode::CONFLICT, msg),
+            AppError::BadRequest(_, msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::InternalError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "An internal error occurred".to_string(),
             ),
-            AppError::BadRequest(msg) => (
+            AppError::WebauthnError(_) => (
                 StatusCode::BAD_REQUEST,
-                "BAD_REQUEST",
-                msg,
+                "Passkey operation failed".to_string(),
             ),
-            AppError::InternalError(msg) => {
-                eprintln!("❌ Internal error: {}", msg);
+            AppError::SerializationError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Data serialization failed".to_string(),
+            ),
+            AppError::StepUpRequired { max_age } => {
+                let prefix = ApiVersion::current().path_prefix();
+                hint = Some(format!("Complete POST {prefix}/auth/step-up/start and {prefix}/auth/step-up/finish, then retry"));
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "INTERNAL_ERROR",
-                    "An internal error occurred".to_string(),
+                    StatusCode::UNAUTHORIZED,
+                    format!("This action requires passkey verification within the last {} seconds", max_age),
                 )
             }
-            AppError::WebauthnError(msg) => {
-                eprintln!("❌ Webauthn error: {}", msg);
-                (
-                    StatusCode::BAD_REQUEST,
-                    "WEBAUTHN_ERROR",
-                    "Passkey operation failed".to_string(),
-                )
-            }
-            AppError::SerializationError(msg) => {
-                eprintln!("❌ Serialization error: {}", msg);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "SERIALIZATION_ERROR",
-                    "Data serialization failed".to_string(),
-                )
+            AppError::RateLimited { message, retry_after: seconds, .. } => {
+                hint = Some(format!("Retry after {} seconds", seconds));
+                retry_after = Some(seconds);
+                (StatusCode::TOO_MANY_REQUESTS, message)
             }
         };
 
+        let legacy = LegacyErrorResponse {
+            error: legacy_kind.to_string(),
+            message: if details.is_empty() { message.clone() } else { describe_fields(&details) },
+            details: hint.clone(),
+        };
+
         let error_response = ErrorResponse {
-            error: error_type.to_string(),
+            error,
             message,
-            details: None,
+            details,
+            hint,
+            retry_after,
+            request_id: request_span::current_request_id(),
         };
 
-        (status, Json(error_response)).into_response()
+        let mut response = (status, Json(error_response)).into_response();
+        if let Some(seconds) = retry_after
+            && let Ok(value) = HeaderValue::from_str(&seconds.to_string())
+        {
+            response.headers_mut().insert(RETRY_AFTER, value);
+        }
+        response.extensions_mut().insert(legacy);
+
+        response
     }
 }
 
@@ -140,4 +431,4 @@ impl From<serde_json::Error> for AppError {
     fn from(err: serde_json::Error) -> Self {
         AppError::SerializationError(err.to_string())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,179 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ed25519_dalek::pkcs8::{DecodePrivateKey as _, DecodePublicKey as _};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey,
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+        OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+};
+use rsa::{pkcs1::DecodeRsaPrivateKey, traits::PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use std::{collections::HashMap, fs, sync::Arc};
+
+use crate::config::JwtConfig;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+
+const LEGACY_HS256_KID: &str = "hs256";
+
+pub struct JwtKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    encoding: Option<EncodingKey>,
+    decoding: DecodingKey,
+    jwk: Option<Jwk>,
+}
+
+pub struct JwtKeys {
+    active_kid: String,
+    keys: HashMap<String, JwtKey>,
+}
+
+impl JwtKeys {
+    pub fn signing_key(&self) -> AppResult<(&JwtKey, &EncodingKey)> {
+        let key = self.keys.get(&self.active_kid)
+            .ok_or_else(|| AppError::InternalError(format!("Active signing key '{}' is not loaded", self.active_kid)))?;
+        let encoding = key.encoding.as_ref()
+            .ok_or_else(|| AppError::InternalError(format!("Key '{}' has no private key", key.kid)))?;
+        Ok((key, encoding))
+    }
+
+    pub fn verification_key(&self, kid: Option<&str>) -> AppResult<(&JwtKey, &DecodingKey)> {
+        let kid = kid.unwrap_or(LEGACY_HS256_KID);
+        self.keys.get(kid)
+            .map(|key| (key, &key.decoding))
+            .ok_or_else(|| AppError::AuthenticationError(ErrorCode::InvalidToken, format!("Unknown signing key '{}'", kid)))
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<Jwk> = self.keys.values().filter_map(|key| key.jwk.clone()).collect();
+        keys.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+        JwkSet { keys }
+    }
+}
+
+fn common_parameters(kid: &str, algorithm: KeyAlgorithm) -> CommonParameters {
+    CommonParameters {
+        public_key_use: Some(PublicKeyUse::Signature),
+        key_algorithm: Some(algorithm),
+        key_id: Some(kid.to_string()),
+        ..Default::default()
+    }
+}
+
+fn load_eddsa_key(kid: &str, pem: &str) -> AppResult<JwtKey> {
+    let (encoding, public_bytes) = if pem.contains("PRIVATE KEY") {
+        let signing_key = ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+            .map_err(|e| AppError::InternalError(format!("Invalid Ed25519 private key for '{}': {}", kid, e)))?;
+        let encoding = EncodingKey::from_ed_pem(pem.as_bytes())
+            .map_err(|e| AppError::InternalError(format!("Invalid Ed25519 private key for '{}': {}", kid, e)))?;
+        (Some(encoding), signing_key.verifying_key().to_bytes())
+    } else {
+        let verifying_key = ed25519_dalek::VerifyingKey::from_public_key_pem(pem)
+            .map_err(|e| AppError::InternalError(format!("Invalid Ed25519 public key for '{}': {}", kid, e)))?;
+        (None, verifying_key.to_bytes())
+    };
+
+    let jwk = Jwk {
+        common: common_parameters(kid, KeyAlgorithm::EdDSA),
+        algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+            key_type: OctetKeyPairType::OctetKeyPair,
+            curve: EllipticCurve::Ed25519,
+            x: URL_SAFE_NO_PAD.encode(public_bytes),
+        }),
+    };
+
+    finish_asymmetric_key(kid, Algorithm::EdDSA, encoding, jwk)
+}
+
+fn load_rs256_key(kid: &str, pem: &str) -> AppResult<JwtKey> {
+    let (encoding, public_key) = if pem.contains("PRIVATE KEY") {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+            .map_err(|e| AppError::InternalError(format!("Invalid RSA private key for '{}': {}", kid, e)))?;
+        let encoding = EncodingKey::from_rsa_pem(pem.as_bytes())
+            .map_err(|e| AppError::InternalError(format!("Invalid RSA private key for '{}': {}", kid, e)))?;
+        (Some(encoding), private_key.to_public_key())
+    } else {
+        let public_key = RsaPublicKey::from_public_key_pem(pem)
+            .map_err(|e| AppError::InternalError(format!("Invalid RSA public key for '{}': {}", kid, e)))?;
+        (None, public_key)
+    };
+
+    let jwk = Jwk {
+        common: common_parameters(kid, KeyAlgorithm::RS256),
+        algorithm: AlgorithmParameters::RSA(RSAKeyParameters {
+            key_type: RSAKeyType::RSA,
+            n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+            e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+        }),
+    };
+
+    finish_asymmetric_key(kid, Algorithm::RS256, encoding, jwk)
+}
+
+fn finish_asymmetric_key(kid: &str, algorithm: Algorithm, encoding: Option<EncodingKey>, jwk: Jwk) -> AppResult<JwtKey> {
+    let decoding = DecodingKey::from_jwk(&jwk)
+        .map_err(|e| AppError::InternalError(format!("Failed to build verification key for '{}': {}", kid, e)))?;
+
+    Ok(JwtKey {
+        kid: kid.to_string(),
+        algorithm,
+        encoding,
+        decoding,
+        jwk: Some(jwk),
+    })
+}
+
+fn load_key_entry(entry: &str) -> AppResult<JwtKey> {
+    let mut parts = entry.splitn(3, ':');
+    let (Some(kid), Some(alg), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(AppError::InternalError(format!("JWT_KEYS entry '{}' must be kid:ALG:path", entry)));
+    };
+
+    let pem = fs::read_to_string(path.trim())
+        .map_err(|e| AppError::InternalError(format!("Failed to read key file '{}': {}", path, e)))?;
+
+    match alg.trim() {
+        "EdDSA" => load_eddsa_key(kid.trim(), &pem),
+        "RS256" => load_rs256_key(kid.trim(), &pem),
+        other => Err(AppError::InternalError(format!("Unsupported JWT algorithm '{}' for key '{}'", other, kid))),
+    }
+}
+
+pub fn init_jwt_keys(config: &JwtConfig) -> AppResult<Arc<JwtKeys>> {
+    let mut keys = HashMap::new();
+    let mut first_signing_kid = None;
+
+    for entry in &config.keys {
+        let key = load_key_entry(entry)?;
+        if first_signing_kid.is_none() && key.encoding.is_some() {
+            first_signing_kid = Some(key.kid.clone());
+        }
+        keys.insert(key.kid.clone(), key);
+    }
+
+    if let Some(secret) = &config.secret {
+        keys.insert(LEGACY_HS256_KID.to_string(), JwtKey {
+            kid: LEGACY_HS256_KID.to_string(),
+            algorithm: Algorithm::HS256,
+            encoding: Some(EncodingKey::from_secret(secret.as_bytes())),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+        });
+    }
+
+    let active_kid = config.active_kid.clone()
+        .or(first_signing_kid)
+        .or_else(|| keys.contains_key(LEGACY_HS256_KID).then(|| LEGACY_HS256_KID.to_string()))
+        .ok_or_else(|| AppError::InternalError("Either JWT_KEYS or JWT_SECRET must be set in .env".to_string()))?;
+
+    match keys.get(&active_kid) {
+        Some(key) if key.encoding.is_some() => {}
+        Some(_) => return Err(AppError::InternalError(format!("JWT_ACTIVE_KID '{}' has no private key", active_kid))),
+        None => return Err(AppError::InternalError(format!("JWT_ACTIVE_KID '{}' is not listed in JWT_KEYS", active_kid))),
+    }
+
+    tracing::info!(active_kid = %active_kid, accepted_keys = keys.len(), "JWT keys loaded");
+
+    Ok(Arc::new(JwtKeys { active_kid, keys }))
+}
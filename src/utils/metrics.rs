@@ -0,0 +1,132 @@
+use mongodb::event::{command::CommandEvent, EventHandler};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::sync::Arc;
+
+use crate::utils::error::{AppError, AppResult};
+
+pub const AUTHENTICATION: &str = "authentication";
+pub const REGISTRATION: &str = "registration";
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    votes_cast: IntCounterVec,
+    sse_connections: IntGauge,
+    webauthn_ceremonies: IntCounterVec,
+    mongodb_command_duration: HistogramVec,
+}
+
+pub struct SseConnectionGuard {
+    gauge: IntGauge,
+}
+
+impl Drop for SseConnectionGuard {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
+
+fn metric_error(e: prometheus::Error) -> AppError {
+    AppError::InternalError(format!("Failed to register metric: {}", e))
+}
+
+pub fn init_metrics() -> AppResult<Arc<Metrics>> {
+    let registry = Registry::new();
+
+    let http_requests = IntCounterVec::new(
+        Opts::new("http_requests_total", "HTTP requests handled, by route and status"),
+        &["method", "route", "status"],
+    )
+    .map_err(metric_error)?;
+    let http_request_duration = HistogramVec::new(
+        HistogramOpts::new("http_request_duration_seconds", "HTTP request latency, by route and status"),
+        &["method", "route", "status"],
+    )
+    .map_err(metric_error)?;
+    let votes_cast = IntCounterVec::new(
+        Opts::new("votes_cast_total", "Votes cast, by poll type"),
+        &["poll_type"],
+    )
+    .map_err(metric_error)?;
+    let sse_connections = IntGauge::new("sse_connections_active", "Open poll update streams").map_err(metric_error)?;
+    let webauthn_ceremonies = IntCounterVec::new(
+        Opts::new("webauthn_ceremonies_total", "WebAuthn ceremonies completed, by outcome"),
+        &["ceremony", "outcome"],
+    )
+    .map_err(metric_error)?;
+    let mongodb_command_duration = HistogramVec::new(
+        HistogramOpts::new("mongodb_command_duration_seconds", "MongoDB command latency")
+            .buckets(vec![0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5]),
+        &["command", "outcome"],
+    )
+    .map_err(metric_error)?;
+
+    registry.register(Box::new(http_requests.clone())).map_err(metric_error)?;
+    registry.register(Box::new(http_request_duration.clone())).map_err(metric_error)?;
+    registry.register(Box::new(votes_cast.clone())).map_err(metric_error)?;
+    registry.register(Box::new(sse_connections.clone())).map_err(metric_error)?;
+    registry.register(Box::new(webauthn_ceremonies.clone())).map_err(metric_error)?;
+    registry.register(Box::new(mongodb_command_duration.clone())).map_err(metric_error)?;
+
+    Ok(Arc::new(Metrics {
+        registry,
+        http_requests,
+        http_request_duration,
+        votes_cast,
+        sse_connections,
+        webauthn_ceremonies,
+        mongodb_command_duration,
+    }))
+}
+
+impl Metrics {
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration.with_label_values(&labels).observe(seconds);
+    }
+
+    pub fn record_vote(&self, poll_type: &str) {
+        self.votes_cast.with_label_values(&[poll_type]).inc();
+    }
+
+    pub fn track_sse_connection(&self) -> SseConnectionGuard {
+        self.sse_connections.inc();
+        SseConnectionGuard { gauge: self.sse_connections.clone() }
+    }
+
+    pub fn record_webauthn<T>(&self, ceremony: &str, result: &AppResult<T>) {
+        let outcome = if result.is_ok() { "success" } else { "failure" };
+        self.webauthn_ceremonies.with_label_values(&[ceremony, outcome]).inc();
+    }
+
+    pub fn command_event_handler(self: &Arc<Self>) -> EventHandler<CommandEvent> {
+        let metrics = Arc::clone(self);
+
+        EventHandler::callback(move |event| {
+            let (command, outcome, duration) = match &event {
+                CommandEvent::Succeeded(event) => (&event.command_name, "success", event.duration),
+                CommandEvent::Failed(event) => (&event.command_name, "failure", event.duration),
+                _ => return,
+            };
+
+            metrics
+                .mongodb_command_duration
+                .with_label_values(&[command.as_str(), outcome])
+                .observe(duration.as_secs_f64());
+        })
+    }
+
+    pub fn render(&self) -> AppResult<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode metrics: {}", e)))?;
+
+        String::from_utf8(buffer).map_err(|e| AppError::InternalError(format!("Failed to encode metrics: {}", e)))
+    }
+}
@@ -1,3 +1,23 @@
 pub mod webauthn;
 pub mod session;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod refresh_token;
+pub mod jwt_keys;
+pub mod recovery_codes;
+pub mod passkeys;
+pub mod step_up;
+pub mod authenticator_policy;
+pub mod security_events;
+pub mod auth_guard;
+pub mod roles;
+pub mod organizations;
+pub mod api_tokens;
+pub mod csrf;
+pub mod origins;
+pub mod telemetry;
+pub mod metrics;
+pub mod background;
+pub mod shutdown;
+pub mod validation;
+pub mod rate_limiter;
+pub mod challenges;
@@ -0,0 +1,52 @@
+use mongodb::{bson::{doc, oid::ObjectId}, Database};
+
+use crate::middleware::auth_user::AuthUser;
+use crate::models::{
+    organization_models::{Membership, OrgRole},
+    poll_models::Poll,
+    user_models::Role,
+};
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+
+pub async fn membership(db: &Database, organization_id: ObjectId, user_id: ObjectId) -> AppResult<Option<Membership>> {
+    let membership = db.collection::<Membership>("memberships")
+        .find_one(doc! { "organization_id": organization_id, "user_id": user_id })
+        .await?;
+
+    Ok(membership)
+}
+
+pub async fn require_membership(
+    db: &Database,
+    organization_id: ObjectId,
+    user_id: ObjectId,
+    minimum: OrgRole,
+) -> AppResult<Membership> {
+    let membership = membership(db, organization_id, user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::OrganizationNotFound, "Organization not found".to_string()))?;
+
+    if membership.role < minimum {
+        return Err(AppError::Forbidden(ErrorCode::OrganizationPermissionDenied, "You do not have permission to manage this organization".to_string()));
+    }
+
+    Ok(membership)
+}
+
+pub async fn ensure_poll_access(db: &Database, poll: &Poll, user: Option<&AuthUser>) -> AppResult<()> {
+    let Some(organization_id) = poll.organization_id else {
+        return Ok(());
+    };
+
+    let allowed = match user {
+        Some(user) if user.role >= Role::Admin => true,
+        Some(user) => membership(db, organization_id, user.id).await?.is_some(),
+        None => false,
+    };
+
+    if !allowed {
+        return Err(AppError::NotFound(ErrorCode::PollNotFound, "Poll not found".to_string()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+use webauthn_rs::prelude::Url;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginPattern {
+    Exact(String),
+    Subdomains { scheme: String, domain: String, port: Option<u16> },
+}
+
+pub fn normalize_origin(value: &str) -> Option<String> {
+    let url = Url::parse(value.trim()).ok()?;
+    let origin = url.origin();
+    origin.is_tuple().then(|| origin.ascii_serialization())
+}
+
+impl OriginPattern {
+    pub fn parse(value: &str) -> Result<OriginPattern, String> {
+        let value = value.trim().trim_end_matches('/');
+        let (scheme, rest) = value
+            .split_once("://")
+            .ok_or_else(|| format!("'{}' is not an origin (expected scheme://host)", value))?;
+
+        let Some(domain) = rest.strip_prefix("*.") else {
+            return normalize_origin(value)
+                .map(OriginPattern::Exact)
+                .ok_or_else(|| format!("'{}' is not a valid origin", value));
+        };
+
+        let url = Url::parse(&format!("{}://{}", scheme, domain))
+            .map_err(|e| format!("'{}' is not a valid wildcard origin: {}", value, e))?;
+        let domain = url
+            .domain()
+            .filter(|domain| psl::domain(domain.as_bytes()).is_some())
+            .ok_or_else(|| format!("'{}' must name a registrable domain after '*.', not a public suffix", value))?;
+
+        Ok(OriginPattern::Subdomains {
+            scheme: url.scheme().to_string(),
+            domain: domain.to_string(),
+            port: url.port(),
+        })
+    }
+
+    pub fn matches(&self, origin: &str) -> bool {
+        match self {
+            OriginPattern::Exact(expected) => normalize_origin(origin).as_deref() == Some(expected.as_str()),
+            OriginPattern::Subdomains { scheme, domain, port } => {
+                let Ok(url) = Url::parse(origin.trim()) else {
+                    return false;
+                };
+
+                url.scheme() == scheme
+                    && url.port() == *port
+                    && url.domain().is_some_and(|host| host.ends_with(&format!(".{}", domain)))
+            }
+        }
+    }
+
+    pub fn scheme(&self) -> &str {
+        match self {
+            OriginPattern::Exact(origin) => origin.split("://").next().unwrap_or_default(),
+            OriginPattern::Subdomains { scheme, .. } => scheme,
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        let host = match self {
+            OriginPattern::Exact(origin) => Url::parse(origin).ok().and_then(|url| url.host_str().map(str::to_string)),
+            OriginPattern::Subdomains { domain, .. } => Some(domain.clone()),
+        };
+
+        host.is_some_and(|host| host == "localhost" || host == "127.0.0.1" || host.ends_with(".localhost"))
+    }
+
+    pub fn base_url(&self) -> Option<Url> {
+        match self {
+            OriginPattern::Exact(origin) => Url::parse(origin).ok(),
+            OriginPattern::Subdomains { scheme, domain, port } => {
+                let port = port.map(|port| format!(":{}", port)).unwrap_or_default();
+                Url::parse(&format!("{}://{}{}", scheme, domain, port)).ok()
+            }
+        }
+    }
+}
+
+impl fmt::Display for OriginPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OriginPattern::Exact(origin) => write!(f, "{}", origin),
+            OriginPattern::Subdomains { scheme, domain, port: Some(port) } => write!(f, "{}://*.{}:{}", scheme, domain, port),
+            OriginPattern::Subdomains { scheme, domain, port: None } => write!(f, "{}://*.{}", scheme, domain),
+        }
+    }
+}
+
+pub fn any_matches(patterns: &[OriginPattern], origin: &str) -> bool {
+    patterns.iter().any(|pattern| pattern.matches(origin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(value: &str) -> OriginPattern {
+        OriginPattern::parse(value).unwrap()
+    }
+
+    #[test]
+    fn exact_origin_is_normalized() {
+        assert_eq!(pattern("https://App.Example.com/"), OriginPattern::Exact("https://app.example.com".to_string()));
+        assert_eq!(pattern("https://example.com:443"), OriginPattern::Exact("https://example.com".to_string()));
+        assert!(pattern("https://example.com").matches("https://example.com/"));
+    }
+
+    #[test]
+    fn wildcard_matches_subdomains_only() {
+        let wildcard = pattern("https://*.example.com");
+
+        assert!(wildcard.matches("https://app.example.com"));
+        assert!(wildcard.matches("https://a.b.example.com"));
+        assert!(!wildcard.matches("https://example.com"));
+        assert!(!wildcard.matches("https://evilexample.com"));
+        assert!(!wildcard.matches("https://example.com.evil.org"));
+    }
+
+    #[test]
+    fn wildcard_requires_a_registrable_domain() {
+        assert!(OriginPattern::parse("https://*.com").is_err());
+        assert!(OriginPattern::parse("https://*.localhost").is_err());
+        assert!(OriginPattern::parse("https://*.co.uk").is_err());
+        assert!(OriginPattern::parse("https://*.github.io").is_err());
+        assert!(OriginPattern::parse("https://*.example.co.uk").is_ok());
+        assert!(OriginPattern::parse("https://*.app.github.io").is_ok());
+        assert!(OriginPattern::parse("example.com").is_err());
+    }
+
+    #[test]
+    fn port_must_match() {
+        let exact = pattern("http://localhost:3000");
+        assert!(exact.matches("http://localhost:3000"));
+        assert!(!exact.matches("http://localhost:3001"));
+        assert!(!exact.matches("http://localhost"));
+
+        let wildcard = pattern("https://*.example.com:8443");
+        assert!(wildcard.matches("https://app.example.com:8443"));
+        assert!(!wildcard.matches("https://app.example.com"));
+        assert_eq!(wildcard.to_string(), "https://*.example.com:8443");
+    }
+
+    #[test]
+    fn scheme_must_match() {
+        let exact = pattern("https://example.com");
+        assert!(!exact.matches("http://example.com"));
+        assert_eq!(exact.scheme(), "https");
+
+        let wildcard = pattern("https://*.example.com");
+        assert!(!wildcard.matches("http://app.example.com"));
+        assert_eq!(wildcard.scheme(), "https");
+    }
+}
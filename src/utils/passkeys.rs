@@ -0,0 +1,232 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime, Document},
+    Database,
+};
+use webauthn_rs::prelude::*;
+
+use crate::middleware::client_context::ClientContext;
+use crate::state::AppState;
+use crate::models::user_models::Role;
+use crate::utils::authenticator_policy::{AuthenticatorPolicy, PolicyDecision};
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::utils::metrics;
+use crate::utils::security_events::{self, NewSecurityEvent};
+
+pub struct AuthenticatedPasskey {
+    pub passkey_doc: Document,
+    pub security_warning: Option<String>,
+}
+
+pub fn encode_credential_id(credential_id: &[u8]) -> String {
+    STANDARD.encode(credential_id)
+}
+
+pub async fn store_passkey(
+    db: &Database,
+    user_id: ObjectId,
+    username: &str,
+    passkey: &Passkey,
+    decision: &PolicyDecision,
+) -> AppResult<()> {
+    let passkeys = db.collection::<Document>("passkeys");
+    let credential_id_b64 = encode_credential_id(passkey.cred_id());
+
+    if passkeys.find_one(doc! { "credential_id": &credential_id_b64 }).await?.is_some() {
+        return Err(AppError::Conflict(ErrorCode::PasskeyAlreadyRegistered, "This passkey is already registered".to_string()));
+    }
+
+    passkeys
+        .insert_one(
+            doc! {
+                "credential_id": credential_id_b64,
+                "user_id": user_id,
+                "username": username,
+                "passkey": mongodb::bson::to_document(passkey)?,
+                "user_verified": decision.user_verified,
+                "policy_decision": mongodb::bson::to_bson(decision)?,
+                "created_at": BsonDateTime::now(),
+                "last_used_at": BsonDateTime::now(),
+            },
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn passkey_from_doc(passkey_doc: &Document) -> AppResult<Passkey> {
+    let passkey_doc = passkey_doc
+        .get_document("passkey")
+        .map_err(|e| AppError::InternalError(format!("Failed to get passkey document: {}", e)))?;
+
+    Ok(mongodb::bson::from_document(passkey_doc.clone())?)
+}
+
+pub async fn passkeys_for_user(db: &Database, user_id: ObjectId) -> AppResult<Vec<Passkey>> {
+    let passkey_docs: Vec<Document> = db.collection::<Document>("passkeys")
+        .find(doc! { "user_id": user_id })
+        .await?
+        .try_collect()
+        .await?;
+
+    passkey_docs.iter().map(passkey_from_doc).collect()
+}
+
+pub async fn has_passkey_for_role(
+    db: &Database,
+    policy: &AuthenticatorPolicy,
+    user_id: ObjectId,
+    role: Role,
+) -> AppResult<bool> {
+    let passkey_docs: Vec<Document> = db.collection::<Document>("passkeys")
+        .find(doc! { "user_id": user_id, "clone_suspected": { "$ne": true } })
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(passkey_docs.iter().any(|passkey_doc| {
+        passkey_doc
+            .get("policy_decision")
+            .and_then(|decision| mongodb::bson::from_bson::<PolicyDecision>(decision.clone()).ok())
+            .is_some_and(|decision| policy.satisfies_role(role, &decision))
+    }))
+}
+
+pub async fn authentication_passkeys(state: &AppState, user_id: ObjectId) -> AppResult<Vec<Passkey>> {
+    let mut filter = doc! { "user_id": user_id };
+    if state.authenticator_policy.blocks_cloned_credentials() {
+        filter.insert("clone_suspected", doc! { "$ne": true });
+    }
+
+    let passkey_docs: Vec<Document> = state.db.collection::<Document>("passkeys")
+        .find(filter)
+        .await?
+        .try_collect()
+        .await?;
+
+    // Counters are compared against the stored value in finish_authentication, so that a
+    // regression can be flagged and audited instead of failing inside webauthn-rs.
+    passkey_docs
+        .iter()
+        .map(|passkey_doc| {
+            let mut credential: Credential = passkey_from_doc(passkey_doc)?.into();
+            credential.counter = 0;
+            Ok(Passkey::from(credential))
+        })
+        .collect()
+}
+
+pub async fn credential_ids_for_user(db: &Database, user_id: ObjectId) -> AppResult<Vec<CredentialID>> {
+    let passkeys = passkeys_for_user(db, user_id).await?;
+
+    Ok(passkeys.iter().map(|passkey| passkey.cred_id().clone()).collect())
+}
+
+pub async fn finish_authentication(
+    state: &AppState,
+    client: &ClientContext,
+    credential: &PublicKeyCredential,
+    auth_state: &PasskeyAuthentication,
+) -> AppResult<AuthenticatedPasskey> {
+    let result = verify_authentication(state, client, credential, auth_state).await;
+    state.metrics.record_webauthn(metrics::AUTHENTICATION, &result);
+    result
+}
+
+async fn verify_authentication(
+    state: &AppState,
+    client: &ClientContext,
+    credential: &PublicKeyCredential,
+    auth_state: &PasskeyAuthentication,
+) -> AppResult<AuthenticatedPasskey> {
+    let auth_result = state.webauthn
+        .finish_passkey_authentication(credential, auth_state)
+        .map_err(|e| AppError::AuthenticationError(ErrorCode::PasskeyVerificationFailed, format!("Authentication verification failed: {}", e)))?;
+
+    if state.authenticator_policy.requires_user_verification() && !auth_result.user_verified() {
+        return Err(AppError::AuthenticationError(ErrorCode::UserVerificationRequired, "User verification is required".to_string()));
+    }
+
+    let credential_id_base64 = encode_credential_id(auth_result.cred_id());
+    let passkeys = state.db.collection::<Document>("passkeys");
+
+    let passkey_doc = passkeys
+        .find_one(doc! { "credential_id": &credential_id_base64 })
+        .await?
+        .ok_or_else(|| AppError::NotFound(ErrorCode::PasskeyNotFound, "Passkey not found".to_string()))?;
+
+    let mut passkey = passkey_from_doc(&passkey_doc)?;
+    let stored_counter = Credential::from(passkey.clone()).counter;
+    let counter = auth_result.counter();
+
+    let clone_suspected = (counter > 0 || stored_counter > 0) && counter <= stored_counter;
+    if clone_suspected {
+        let blocked = state.authenticator_policy.blocks_cloned_credentials();
+        let user_id = passkey_doc
+            .get_object_id("user_id")
+            .map_err(|e| AppError::InternalError(format!("Failed to get user_id: {}", e)))?;
+        let username = passkey_doc
+            .get_str("username")
+            .map_err(|e| AppError::InternalError(format!("Failed to get username: {}", e)))?;
+
+        tracing::warn!(
+            credential_id = %credential_id_base64,
+            username = %username,
+            stored_counter,
+            received_counter = counter,
+            "Signature counter regression detected"
+        );
+
+        passkeys
+            .update_one(
+                doc! { "credential_id": &credential_id_base64 },
+                doc! { "$set": { "clone_suspected": true, "clone_detected_at": BsonDateTime::now() } },
+            )
+            .await?;
+
+        security_events::record_security_event(
+            &state.db,
+            NewSecurityEvent::new(
+                user_id,
+                username,
+                security_events::PASSKEY_CLONE_SUSPECTED,
+                if blocked { "blocked" } else { "allowed" },
+                client,
+            )
+            .credential(&credential_id_base64)
+            .detail(format!("Signature counter went from {} to {}", stored_counter, counter)),
+        )
+        .await?;
+
+        if blocked {
+            return Err(AppError::AuthenticationError(
+                ErrorCode::PasskeyDisabled,
+                "This passkey may have been cloned and has been disabled. Sign in with another passkey or a recovery code".to_string(),
+            ));
+        }
+    }
+
+    passkey.update_credential(&auth_result);
+
+    passkeys
+        .update_one(
+            doc! { "credential_id": &credential_id_base64 },
+            doc! {
+                "$set": {
+                    "passkey": mongodb::bson::to_document(&passkey)?,
+                    "counter": counter.max(stored_counter) as i64,
+                    "user_verified": auth_result.user_verified(),
+                    "last_used_at": BsonDateTime::now(),
+                }
+            },
+        )
+        .await?;
+
+    Ok(AuthenticatedPasskey {
+        passkey_doc,
+        security_warning: clone_suspected.then(|| {
+            "This passkey reported an unexpected signature counter and may have been cloned. Review your security activity and consider removing it".to_string()
+        }),
+    })
+}
@@ -0,0 +1,263 @@
+use chrono::Utc;
+use mongodb::{
+    bson::{doc, DateTime as BsonDateTime, Document},
+    options::ReturnDocument,
+    Database,
+};
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use crate::config::{RateBudget, RateLimitBackend, RateLimitConfig};
+use crate::db::indexes::is_duplicate_key;
+use crate::utils::error::AppResult;
+
+pub struct RateDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    pub retry_after: i64,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: f64,
+    period: f64,
+}
+
+enum BucketStore {
+    Memory(Mutex<HashMap<String, Bucket>>),
+    MongoDb(Arc<Database>),
+}
+
+pub struct RateLimiter {
+    enabled: bool,
+    store: BucketStore,
+    route_budgets: HashMap<String, RateBudget>,
+}
+
+pub fn init_rate_limiter(config: &RateLimitConfig, db: &Arc<Database>) -> Arc<RateLimiter> {
+    let store = match config.backend {
+        RateLimitBackend::Memory => BucketStore::Memory(Mutex::new(HashMap::new())),
+        RateLimitBackend::MongoDb => BucketStore::MongoDb(Arc::clone(db)),
+    };
+
+    tracing::info!(
+        enabled = config.enabled,
+        store = match config.backend { RateLimitBackend::Memory => "memory", RateLimitBackend::MongoDb => "mongodb" },
+        routes = config.route_budgets.len(),
+        "Rate limiter loaded"
+    );
+
+    Arc::new(RateLimiter {
+        enabled: config.enabled,
+        store,
+        route_budgets: config.route_budgets.clone(),
+    })
+}
+
+fn now_seconds() -> f64 {
+    Utc::now().timestamp_millis() as f64 / 1000.0
+}
+
+fn decide(budget: RateBudget, tokens: f64, allowed: bool) -> RateDecision {
+    let rate = budget.capacity as f64 / budget.period_seconds as f64;
+    RateDecision {
+        allowed,
+        limit: budget.capacity,
+        remaining: tokens.max(0.0).floor() as u32,
+        retry_after: if allowed { 0 } else { ((1.0 - tokens) / rate).ceil().max(1.0) as i64 },
+    }
+}
+
+impl RateLimiter {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn budget(&self, route: &str) -> Option<RateBudget> {
+        self.route_budgets.get(route).copied()
+    }
+
+    fn longest_period(&self) -> u32 {
+        self.route_budgets
+            .values()
+            .map(|budget| budget.period_seconds)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub async fn take(&self, route: &str, budget: RateBudget, key: &str) -> AppResult<RateDecision> {
+        let bucket_key = format!("{}:{}", route, key);
+
+        match &self.store {
+            BucketStore::Memory(buckets) => Ok(Self::take_memory(buckets, budget, bucket_key, now_seconds())),
+            BucketStore::MongoDb(db) => Self::take_mongodb(db, budget, &bucket_key).await,
+        }
+    }
+
+    fn take_memory(buckets: &Mutex<HashMap<String, Bucket>>, budget: RateBudget, key: String, now: f64) -> RateDecision {
+        let capacity = budget.capacity as f64;
+        let period = budget.period_seconds as f64;
+        let rate = capacity / period;
+
+        let mut buckets = buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(key).or_insert(Bucket { tokens: capacity, updated_at: now, period });
+
+        bucket.tokens = (bucket.tokens + (now - bucket.updated_at).max(0.0) * rate).min(capacity);
+        bucket.updated_at = now;
+        bucket.period = period;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        decide(budget, bucket.tokens, allowed)
+    }
+
+    async fn take_mongodb(db: &Database, budget: RateBudget, key: &str) -> AppResult<RateDecision> {
+        let capacity = budget.capacity as f64;
+        let rate_per_ms = capacity / (budget.period_seconds as f64 * 1000.0);
+
+        // Refill and consume in a single pipeline update so concurrent instances never race on a bucket.
+        // Elapsed time comes from the server's `$$NOW`, so instance clock skew cannot mint tokens.
+        let pipeline = vec![
+            doc! { "$set": {
+                "tokens": { "$min": [
+                    capacity,
+                    { "$add": [
+                        { "$ifNull": ["$tokens", capacity] },
+                        { "$multiply": [
+                            { "$max": [0, { "$subtract": ["$$NOW", { "$ifNull": ["$updated_at", "$$NOW"] }] }] },
+                            rate_per_ms,
+                        ] },
+                    ] },
+                ] },
+                "updated_at": "$$NOW",
+            } },
+            doc! { "$set": { "allowed": { "$gte": ["$tokens", 1.0] } } },
+            doc! { "$set": { "tokens": { "$cond": ["$allowed", { "$subtract": ["$tokens", 1.0] }, "$tokens"] } } },
+        ];
+
+        let buckets = db.collection::<Document>("rate_limit_buckets");
+        let mut attempt = 0;
+        let bucket = loop {
+            let result = buckets
+                .find_one_and_update(doc! { "_id": key }, pipeline.clone())
+                .upsert(true)
+                .return_document(ReturnDocument::After)
+                .await;
+
+            match result {
+                // Two instances creating the same bucket at once: the loser retries against the winner's document.
+                Err(e) if attempt == 0 && is_duplicate_key(&e) => attempt += 1,
+                other => break other?,
+            }
+        };
+
+        let (tokens, allowed) = match &bucket {
+            Some(bucket) => (
+                bucket.get_f64("tokens").unwrap_or(0.0),
+                bucket.get_bool("allowed").unwrap_or(false),
+            ),
+            None => (capacity - 1.0, true),
+        };
+
+        Ok(decide(budget, tokens, allowed))
+    }
+
+    pub async fn purge_idle_buckets(&self) -> AppResult<()> {
+        match &self.store {
+            BucketStore::Memory(buckets) => {
+                let now = now_seconds();
+                let mut buckets = buckets.lock().unwrap_or_else(|e| e.into_inner());
+                let before = buckets.len();
+                buckets.retain(|_, bucket| now - bucket.updated_at < bucket.period);
+
+                if buckets.len() < before {
+                    tracing::debug!(deleted = before - buckets.len(), "Purged idle rate limit buckets");
+                }
+            }
+            BucketStore::MongoDb(db) => {
+                let idle_before = BsonDateTime::from_millis(
+                    BsonDateTime::now().timestamp_millis() - self.longest_period() as i64 * 1000,
+                );
+                let result = db.collection::<Document>("rate_limit_buckets")
+                    .delete_many(doc! { "updated_at": { "$lt": idle_before } })
+                    .await?;
+
+                if result.deleted_count > 0 {
+                    tracing::debug!(deleted = result.deleted_count, "Purged idle rate limit buckets");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: RateBudget = RateBudget { capacity: 3, period_seconds: 30 };
+
+    fn take(buckets: &Mutex<HashMap<String, Bucket>>, now: f64) -> RateDecision {
+        RateLimiter::take_memory(buckets, BUDGET, "polls.vote:user:1".to_string(), now)
+    }
+
+    #[test]
+    fn decide_reports_remaining_and_retry_after() {
+        let allowed = decide(BUDGET, 1.7, true);
+        assert!(allowed.allowed);
+        assert_eq!((allowed.limit, allowed.remaining, allowed.retry_after), (3, 1, 0));
+
+        // One token per 10 seconds: 0.25 tokens left needs 7.5s more, rounded up.
+        let denied = decide(BUDGET, 0.25, false);
+        assert!(!denied.allowed);
+        assert_eq!((denied.remaining, denied.retry_after), (0, 8));
+
+        assert_eq!(decide(BUDGET, 0.999, false).retry_after, 1);
+    }
+
+    #[test]
+    fn bucket_empties_then_refills() {
+        let buckets = Mutex::new(HashMap::new());
+
+        let remaining: Vec<u32> = (0..3).map(|_| take(&buckets, 100.0).remaining).collect();
+        assert_eq!(remaining, vec![2, 1, 0]);
+
+        let denied = take(&buckets, 100.0);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, 10);
+
+        let denied = take(&buckets, 104.0);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, 6);
+
+        let allowed = take(&buckets, 110.0);
+        assert!(allowed.allowed);
+        assert_eq!(allowed.remaining, 0);
+    }
+
+    #[test]
+    fn refill_is_capped_at_capacity() {
+        let buckets = Mutex::new(HashMap::new());
+        take(&buckets, 0.0);
+
+        let decision = take(&buckets, 10_000.0);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, BUDGET.capacity - 1);
+    }
+
+    #[test]
+    fn buckets_are_per_key() {
+        let buckets = Mutex::new(HashMap::new());
+        for _ in 0..3 {
+            take(&buckets, 0.0);
+        }
+
+        let other = RateLimiter::take_memory(&buckets, BUDGET, "polls.vote:user:2".to_string(), 0.0);
+        assert!(other.allowed);
+        assert!(!take(&buckets, 0.0).allowed);
+    }
+}
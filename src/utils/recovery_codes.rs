@@ -0,0 +1,80 @@
+use chrono::Utc;
+use mongodb::{bson::{self, doc, oid::ObjectId}, Database};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::models::user_models::{RecoveryCode, User};
+use crate::utils::error::AppResult;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    format!("{:x}", Sha256::digest(normalize(code).as_bytes()))
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    let chars: Vec<char> = (0..12)
+        .map(|_| RECOVERY_CODE_ALPHABET[rng.gen_range(0..RECOVERY_CODE_ALPHABET.len())] as char)
+        .collect();
+
+    chars
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+pub fn generate_recovery_codes() -> (Vec<String>, Vec<RecoveryCode>) {
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT).map(|_| generate_code()).collect();
+    let hashed = codes
+        .iter()
+        .map(|code| RecoveryCode {
+            code_hash: hash_recovery_code(code),
+            used_at: None,
+        })
+        .collect();
+
+    (codes, hashed)
+}
+
+pub async fn replace_recovery_codes(db: &Database, user_id: ObjectId) -> AppResult<Vec<String>> {
+    let (codes, hashed) = generate_recovery_codes();
+
+    db.collection::<User>("users")
+        .update_one(
+            doc! { "_id": user_id },
+            doc! { "$set": { "recovery_codes": bson::to_bson(&hashed)? } },
+        )
+        .await?;
+
+    Ok(codes)
+}
+
+pub async fn consume_recovery_code(db: &Database, user_id: ObjectId, code: &str) -> AppResult<bool> {
+    let result = db.collection::<User>("users")
+        .update_one(
+            doc! {
+                "_id": user_id,
+                "recovery_codes": {
+                    "$elemMatch": { "code_hash": hash_recovery_code(code), "used_at": null }
+                }
+            },
+            doc! { "$set": { "recovery_codes.$.used_at": Utc::now().to_rfc3339() } },
+        )
+        .await?;
+
+    Ok(result.modified_count == 1)
+}
+
+pub fn remaining_recovery_codes(user: &User) -> usize {
+    user.recovery_codes.iter().filter(|code| code.used_at.is_none()).count()
+}
@@ -0,0 +1,212 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use mongodb::{bson::{doc, oid::ObjectId, DateTime as BsonDateTime}, Database};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::models::session_models::{RefreshToken, Session};
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::state::AppState;
+use crate::utils::{roles, session};
+
+pub struct IssuedSession {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+pub struct RotatedSession {
+    pub user_id: ObjectId,
+    pub username: String,
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+async fn insert_refresh_token(state: &AppState, session_id: ObjectId, user_id: ObjectId) -> AppResult<String> {
+    let token = generate_token();
+    let now = Utc::now();
+
+    let record = RefreshToken {
+        id: ObjectId::new(),
+        session_id,
+        user_id,
+        token_hash: hash_token(&token),
+        created_at: now,
+        expires_at: now + Duration::seconds(state.config.session.refresh_token_max_age),
+        used_at: None,
+    };
+
+    state.db.collection::<RefreshToken>("refresh_tokens")
+        .insert_one(record)
+        .await?;
+
+    Ok(token)
+}
+
+pub async fn issue_session(
+    state: &AppState,
+    user_id: ObjectId,
+    username: &str,
+    display_name: &str,
+) -> AppResult<IssuedSession> {
+    let db = &state.db;
+    let now = Utc::now();
+    let new_session = Session {
+        id: ObjectId::new(),
+        user_id,
+        username: username.to_string(),
+        display_name: display_name.to_string(),
+        created_at: now,
+        last_refreshed_at: now,
+        expires_at: now + Duration::seconds(state.config.session.refresh_token_max_age),
+        revoked_at: None,
+        step_up_at: None,
+    };
+
+    db.collection::<Session>("sessions")
+        .insert_one(&new_session)
+        .await?;
+
+    let refresh_token = insert_refresh_token(state, new_session.id, user_id).await?;
+    let role = roles::user_role(db, user_id).await?;
+    let access_token = session::create_token(&state.jwt_keys, &state.config.session, &user_id, username, display_name, &new_session.id, role)?;
+
+    Ok(IssuedSession {
+        access_token,
+        refresh_token,
+    })
+}
+
+pub async fn rotate_refresh_token(state: &AppState, presented: &str) -> AppResult<RotatedSession> {
+    let db = &state.db;
+    let refresh_tokens = db.collection::<RefreshToken>("refresh_tokens");
+    let token_hash = hash_token(presented);
+    let now = Utc::now();
+
+    let record = refresh_tokens
+        .find_one_and_update(
+            doc! { "token_hash": &token_hash, "used_at": null },
+            doc! { "$set": { "used_at": BsonDateTime::from_chrono(now) } },
+        )
+        .await?;
+
+    let record = match record {
+        Some(record) => record,
+        None => {
+            if let Some(reused) = refresh_tokens.find_one(doc! { "token_hash": &token_hash }).await? {
+                tracing::warn!(session_id = %reused.session_id, "Refresh token reuse detected, revoking session");
+                revoke_session(db, reused.session_id).await?;
+                return Err(AppError::AuthenticationError(ErrorCode::RefreshTokenReused, "Refresh token has already been used".to_string()));
+            }
+            return Err(AppError::AuthenticationError(ErrorCode::RefreshTokenInvalid, "Invalid refresh token".to_string()));
+        }
+    };
+
+    if record.expires_at < now {
+        return Err(AppError::AuthenticationError(ErrorCode::RefreshTokenExpired, "Refresh token has expired".to_string()));
+    }
+
+    let sessions = db.collection::<Session>("sessions");
+    let active_session = sessions
+        .find_one(doc! { "_id": record.session_id, "revoked_at": null })
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::SessionRevoked, "Session has been revoked".to_string()))?;
+
+    sessions
+        .update_one(
+            doc! { "_id": active_session.id },
+            doc! { "$set": {
+                "last_refreshed_at": BsonDateTime::from_chrono(now),
+                "expires_at": BsonDateTime::from_chrono(now + Duration::seconds(state.config.session.refresh_token_max_age)),
+            } },
+        )
+        .await?;
+
+    let role = roles::user_role(db, active_session.user_id).await?;
+    let refresh_token = insert_refresh_token(state, active_session.id, active_session.user_id).await?;
+    let access_token = session::create_token(
+        &state.jwt_keys,
+        &state.config.session,
+        &active_session.user_id,
+        &active_session.username,
+        &active_session.display_name,
+        &active_session.id,
+        role,
+    )?;
+
+    Ok(RotatedSession {
+        user_id: active_session.user_id,
+        username: active_session.username,
+        access_token,
+        refresh_token,
+    })
+}
+
+pub async fn revoke_session(db: &Database, session_id: ObjectId) -> AppResult<()> {
+    db.collection::<Session>("sessions")
+        .update_one(
+            doc! { "_id": session_id, "revoked_at": null },
+            doc! { "$set": { "revoked_at": BsonDateTime::now() } },
+        )
+        .await?;
+
+    db.collection::<RefreshToken>("refresh_tokens")
+        .delete_many(doc! { "session_id": session_id })
+        .await?;
+
+    Ok(())
+}
+
+pub async fn revoke_session_by_refresh_token(db: &Database, presented: &str) -> AppResult<Option<Session>> {
+    let record = db.collection::<RefreshToken>("refresh_tokens")
+        .find_one(doc! { "token_hash": hash_token(presented) })
+        .await?;
+
+    let Some(record) = record else {
+        return Ok(None);
+    };
+
+    let revoked = db.collection::<Session>("sessions")
+        .find_one(doc! { "_id": record.session_id, "revoked_at": null })
+        .await?;
+
+    revoke_session(db, record.session_id).await?;
+
+    Ok(revoked)
+}
+
+pub async fn revoke_user_sessions(db: &Database, user_id: ObjectId) -> AppResult<()> {
+    db.collection::<Session>("sessions")
+        .update_many(
+            doc! { "user_id": user_id, "revoked_at": null },
+            doc! { "$set": { "revoked_at": BsonDateTime::now() } },
+        )
+        .await?;
+
+    db.collection::<RefreshToken>("refresh_tokens")
+        .delete_many(doc! { "user_id": user_id })
+        .await?;
+
+    Ok(())
+}
+
+pub async fn ensure_session_active(db: &Database, session_id: &str) -> AppResult<()> {
+    let session_id = ObjectId::parse_str(session_id)
+        .map_err(|_| AppError::AuthenticationError(ErrorCode::InvalidToken, "Invalid session id in token".to_string()))?;
+
+    db.collection::<Session>("sessions")
+        .find_one(doc! { "_id": session_id, "revoked_at": null })
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::SessionRevoked, "Session has been revoked".to_string()))?;
+
+    Ok(())
+}
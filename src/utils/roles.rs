@@ -0,0 +1,49 @@
+use futures::stream::TryStreamExt;
+use mongodb::{bson::{doc, oid::ObjectId}, Database};
+use crate::config::Config;
+use crate::models::user_models::{Role, User};
+use crate::utils::authenticator_policy::AuthenticatorPolicy;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::utils::passkeys;
+
+pub async fn promote_bootstrap_admins(db: &Database, config: &Config, policy: &AuthenticatorPolicy) -> AppResult<()> {
+    let usernames = &config.admin_usernames;
+    if usernames.is_empty() {
+        return Ok(());
+    }
+
+    let users = db.collection::<User>("users");
+    let candidates: Vec<User> = users
+        .find(doc! { "username": { "$in": usernames }, "role": { "$ne": Role::Admin.as_str() } })
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut promoted = 0;
+    for user in candidates {
+        if !passkeys::has_passkey_for_role(db, policy, user.id, Role::Admin).await? {
+            tracing::warn!(username = %user.username, "Bootstrap admin has no attested hardware passkey, not promoting");
+            continue;
+        }
+
+        users
+            .update_one(doc! { "_id": user.id }, doc! { "$set": { "role": Role::Admin.as_str() } })
+            .await?;
+        promoted += 1;
+    }
+
+    if promoted > 0 {
+        tracing::info!(promoted, "Promoted bootstrap admin accounts");
+    }
+
+    Ok(())
+}
+
+pub async fn user_role(db: &Database, user_id: ObjectId) -> AppResult<Role> {
+    let user = db.collection::<User>("users")
+        .find_one(doc! { "_id": user_id })
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::AccountNotFound, "User no longer exists".to_string()))?;
+
+    Ok(user.role)
+}
@@ -0,0 +1,120 @@
+use chrono::Utc;
+use futures::stream::TryStreamExt;
+use mongodb::{bson::{doc, oid::ObjectId, Document}, Database};
+
+use crate::middleware::client_context::ClientContext;
+use crate::models::security_event_models::SecurityEvent;
+use crate::utils::error::AppResult;
+
+pub const ACCOUNT_REGISTERED: &str = "account_registered";
+pub const PASSKEY_ADDED: &str = "passkey_added";
+pub const PASSKEY_REMOVED: &str = "passkey_removed";
+pub const PASSKEY_CLONE_SUSPECTED: &str = "passkey_clone_suspected";
+pub const LOGIN: &str = "login";
+pub const LOGOUT: &str = "logout";
+pub const STEP_UP: &str = "step_up";
+pub const ACCOUNT_RECOVERY: &str = "account_recovery";
+pub const RECOVERY_CODES_REGENERATED: &str = "recovery_codes_regenerated";
+pub const ROLE_CHANGED: &str = "role_changed";
+pub const API_TOKEN_CREATED: &str = "api_token_created";
+pub const API_TOKEN_REVOKED: &str = "api_token_revoked";
+
+pub const SUCCESS: &str = "success";
+pub const FAILURE: &str = "failure";
+
+pub struct NewSecurityEvent<'a> {
+    pub user_id: ObjectId,
+    pub username: &'a str,
+    pub event_type: &'a str,
+    pub outcome: &'a str,
+    pub client: &'a ClientContext,
+    pub credential_id: Option<&'a str>,
+    pub detail: Option<String>,
+}
+
+impl<'a> NewSecurityEvent<'a> {
+    pub fn new(
+        user_id: ObjectId,
+        username: &'a str,
+        event_type: &'a str,
+        outcome: &'a str,
+        client: &'a ClientContext,
+    ) -> Self {
+        NewSecurityEvent {
+            user_id,
+            username,
+            event_type,
+            outcome,
+            client,
+            credential_id: None,
+            detail: None,
+        }
+    }
+
+    pub fn credential(mut self, credential_id: &'a str) -> Self {
+        self.credential_id = Some(credential_id);
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+pub async fn record_security_event(db: &Database, event: NewSecurityEvent<'_>) -> AppResult<()> {
+    let record = SecurityEvent {
+        id: ObjectId::new(),
+        user_id: event.user_id,
+        username: event.username.to_string(),
+        event_type: event.event_type.to_string(),
+        outcome: event.outcome.to_string(),
+        ip: event.client.ip.clone(),
+        user_agent: event.client.user_agent.clone(),
+        credential_id: event.credential_id.map(str::to_string),
+        detail: event.detail,
+        created_at: Utc::now(),
+    };
+
+    db.collection::<SecurityEvent>("security_events")
+        .insert_one(record)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn record_failed_login(
+    db: &Database,
+    client: &ClientContext,
+    username: &str,
+    credential_id: &str,
+    reason: &str,
+) -> AppResult<()> {
+    let user = db.collection::<Document>("users")
+        .find_one(doc! { "username": username })
+        .await?;
+
+    if let Some(user_id) = user.and_then(|user| user.get_object_id("_id").ok()) {
+        record_security_event(
+            db,
+            NewSecurityEvent::new(user_id, username, LOGIN, FAILURE, client)
+                .credential(credential_id)
+                .detail(reason),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn security_events_for_user(db: &Database, user_id: ObjectId, limit: i64) -> AppResult<Vec<SecurityEvent>> {
+    let events = db.collection::<SecurityEvent>("security_events")
+        .find(doc! { "user_id": user_id })
+        .sort(doc! { "created_at": -1 })
+        .limit(limit)
+        .await?
+        .try_collect()
+        .await?;
+
+    Ok(events)
+}
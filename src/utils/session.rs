@@ -1,47 +1,113 @@
-use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
+use axum::http::{HeaderValue, header::SET_COOKIE};
+use axum::response::Response;
+use jsonwebtoken::{encode, decode, decode_header, Header, Validation};
+use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 use chrono::{Utc, Duration};
-use std::env;
-use crate::utils::error::{AppError, AppResult};
+use crate::config::SessionConfig;
+use crate::models::user_models::Role;
+use crate::utils::csrf;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+use crate::utils::jwt_keys::JwtKeys;
+
+pub const ACCESS_COOKIE: &str = "token";
+pub const REFRESH_COOKIE: &str = "refresh_token";
+// One path per mounted API version, so the refresh cookie only reaches the auth routes.
+const REFRESH_COOKIE_PATHS: &[&str] = &["/api/auth", "/api/v1/auth", "/api/v2/auth"];
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
+    pub username: String,
+    pub display_name: String,
+    pub sid: String,
+    #[serde(default)]
+    pub role: Role,
     pub exp: usize,
 }
 
-pub fn create_token(username: &str) -> AppResult<String> {
-    let secret = env::var("JWT_SECRET")
-        .map_err(|_| AppError::InternalError("JWT_SECRET must be set in .env".to_string()))?;
-    
-    let max_age = env::var("SESSION_MAX_AGE")
-        .unwrap_or_else(|_| "86400".to_string())
-        .parse::<i64>()
-        .unwrap_or(86400);
-    
+pub fn create_token(
+    keys: &JwtKeys,
+    config: &SessionConfig,
+    user_id: &ObjectId,
+    username: &str,
+    display_name: &str,
+    session_id: &ObjectId,
+    role: Role,
+) -> AppResult<String> {
+    let (key, encoding_key) = keys.signing_key()?;
+
     let expiration = Utc::now()
-        .checked_add_signed(Duration::seconds(max_age))
+        .checked_add_signed(Duration::seconds(config.access_token_max_age))
         .ok_or_else(|| AppError::InternalError("Failed to calculate token expiration".to_string()))?
         .timestamp();
 
     let claims = Claims {
-        sub: username.to_string(),
+        sub: user_id.to_hex(),
+        username: username.to_string(),
+        display_name: display_name.to_string(),
+        sid: session_id.to_hex(),
+        role,
         exp: expiration as usize,
     };
 
-    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
-        .map_err(|e| AppError::AuthenticationError(format!("Failed to create token: {}", e)))
+    let mut header = Header::new(key.algorithm);
+    header.kid = Some(key.kid.clone());
+
+    encode(&header, &claims, encoding_key)
+        .map_err(|e| AppError::InternalError(format!("Failed to create token: {}", e)))
 }
 
-pub fn verify_token(token: &str) -> AppResult<Claims> {
-    let secret = env::var("JWT_SECRET")
-        .map_err(|_| AppError::InternalError("JWT_SECRET must be set in .env".to_string()))?;
-    
+pub fn verify_token(keys: &JwtKeys, token: &str) -> AppResult<Claims> {
+    let header = decode_header(token)
+        .map_err(|e| AppError::AuthenticationError(ErrorCode::InvalidToken, format!("Invalid token: {}", e)))?;
+
+    let (key, decoding_key) = keys.verification_key(header.kid.as_deref())?;
+
     decode::<Claims>(
         token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
+        decoding_key,
+        &Validation::new(key.algorithm),
     )
     .map(|data| data.claims)
-    .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))
-}
\ No newline at end of file
+    .map_err(|e| AppError::AuthenticationError(ErrorCode::InvalidToken, format!("Invalid token: {}", e)))
+}
+
+fn cookie(name: &str, value: &str, path: &str, max_age: i64) -> String {
+    format!(
+        "{}={}; Path={}; HttpOnly; Secure; SameSite=None; Max-Age={}",
+        name, value, path, max_age
+    )
+}
+
+fn append_cookie(response: &mut Response, cookie_value: &str) -> AppResult<()> {
+    response.headers_mut().append(
+        SET_COOKIE,
+        HeaderValue::from_str(cookie_value)
+            .map_err(|e| AppError::InternalError(format!("Failed to create cookie header: {}", e)))?
+    );
+    Ok(())
+}
+
+pub fn set_csrf_cookie(response: &mut Response, config: &SessionConfig, csrf_token: &str) -> AppResult<()> {
+    append_cookie(response, &format!(
+        "{}={}; Path=/; Secure; SameSite=None; Max-Age={}",
+        csrf::CSRF_COOKIE, csrf_token, config.refresh_token_max_age
+    ))
+}
+
+pub fn set_session_cookies(response: &mut Response, config: &SessionConfig, access_token: &str, refresh_token: &str) -> AppResult<()> {
+    append_cookie(response, &cookie(ACCESS_COOKIE, access_token, "/", config.access_token_max_age))?;
+    for path in REFRESH_COOKIE_PATHS {
+        append_cookie(response, &cookie(REFRESH_COOKIE, refresh_token, path, config.refresh_token_max_age))?;
+    }
+    Ok(())
+}
+
+pub fn clear_session_cookies(response: &mut Response) -> AppResult<()> {
+    append_cookie(response, &cookie(ACCESS_COOKIE, "", "/", 0))?;
+    for path in REFRESH_COOKIE_PATHS {
+        append_cookie(response, &cookie(REFRESH_COOKIE, "", path, 0))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+}
+
+pub fn init_shutdown() -> Arc<Shutdown> {
+    let (sender, _) = watch::channel(false);
+    Arc::new(Shutdown { sender })
+}
+
+impl Shutdown {
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.sender.subscribe()
+    }
+
+    pub async fn triggered(&self) {
+        let mut receiver = self.subscribe();
+        let _ = receiver.wait_for(|triggered| *triggered).await;
+    }
+}
+
+pub async fn wait_for_signal(shutdown: Arc<Shutdown>) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!(signal = "SIGINT", "Shutdown signal received"),
+        _ = terminate => tracing::info!(signal = "SIGTERM", "Shutdown signal received"),
+    }
+
+    shutdown.trigger();
+}
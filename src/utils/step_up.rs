@@ -0,0 +1,32 @@
+use chrono::{Duration, Utc};
+use mongodb::{bson::{doc, DateTime as BsonDateTime}, Database};
+
+use crate::middleware::auth_user::AuthUser;
+use crate::state::AppState;
+use crate::models::session_models::Session;
+use crate::utils::error::{AppError, AppResult, ErrorCode};
+
+pub async fn record_step_up(db: &Database, user: &AuthUser) -> AppResult<()> {
+    db.collection::<Session>("sessions")
+        .update_one(
+            doc! { "_id": user.session_id, "revoked_at": null },
+            doc! { "$set": { "step_up_at": BsonDateTime::now() } },
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn require_recent_step_up(state: &AppState, user: &AuthUser) -> AppResult<()> {
+    let session = state.db.collection::<Session>("sessions")
+        .find_one(doc! { "_id": user.session_id, "revoked_at": null })
+        .await?
+        .ok_or_else(|| AppError::AuthenticationError(ErrorCode::SessionRevoked, "Session has been revoked".to_string()))?;
+
+    let window = Duration::seconds(state.config.session.step_up_max_age);
+
+    match session.step_up_at {
+        Some(step_up_at) if Utc::now() - step_up_at <= window => Ok(()),
+        _ => Err(AppError::StepUpRequired { max_age: window.num_seconds() }),
+    }
+}
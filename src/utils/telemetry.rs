@@ -0,0 +1,72 @@
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::config::{LogFormat, LoggingConfig};
+use crate::utils::error::{AppError, AppResult};
+
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub fn shutdown(&self) {
+        if let Some(provider) = &self.tracer_provider
+            && let Err(e) = provider.shutdown()
+        {
+            tracing::warn!(error = %e, "Failed to flush OpenTelemetry spans");
+        }
+    }
+}
+
+pub fn init_telemetry(config: &LoggingConfig) -> AppResult<Telemetry> {
+    let filter = EnvFilter::try_new(&config.filter)
+        .map_err(|e| AppError::InternalError(format!("Invalid log filter '{}': {}", config.filter, e)))?;
+
+    let fmt_layer = match config.format {
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().boxed(),
+    };
+
+    let tracer_provider = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .build()
+                .map_err(|e| AppError::InternalError(format!("Failed to create OTLP exporter: {}", e)))?;
+
+            Some(
+                SdkTracerProvider::builder()
+                    .with_batch_exporter(exporter)
+                    .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+                    .build(),
+            )
+        }
+        None => None,
+    };
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let otel_layer = tracer_provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("backend")));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .try_init()
+        .map_err(|e| AppError::InternalError(format!("Failed to install tracing subscriber: {}", e)))?;
+
+    if let Some(endpoint) = &config.otlp_endpoint {
+        tracing::info!(endpoint = %endpoint, service = %config.service_name, "Exporting traces over OTLP");
+    }
+
+    Ok(Telemetry { tracer_provider })
+}
@@ -0,0 +1,229 @@
+use axum::{
+    extract::{FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::ValidationConfig;
+use crate::state::AppState;
+use crate::utils::error::{AppError, AppResult, ErrorCode, FieldError};
+
+pub trait Validate {
+    fn validate(&mut self, v: &mut Validator);
+}
+
+pub struct Validator<'a> {
+    limits: &'a ValidationConfig,
+    errors: Vec<FieldError>,
+}
+
+pub fn normalize(value: &str) -> String {
+    value.trim().nfc().collect()
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 { format!("1 {}", noun) } else { format!("{} {}s", count, noun) }
+}
+
+impl<'a> Validator<'a> {
+    pub fn new(limits: &'a ValidationConfig) -> Self {
+        Validator { limits, errors: Vec::new() }
+    }
+
+    pub fn limits(&self) -> &'a ValidationConfig {
+        self.limits
+    }
+
+    pub fn reject(&mut self, field: impl Into<String>, code: ErrorCode, message: impl Into<String>) {
+        self.errors.push(FieldError::new(field, code, message));
+    }
+
+    /// Checks a value without normalising it, for names that must match what is already stored.
+    pub fn present(&mut self, field: impl Into<String>, value: &str) -> bool {
+        if value.trim().is_empty() {
+            self.reject(field, ErrorCode::Required, "Is required");
+            return false;
+        }
+        true
+    }
+
+    pub fn required(&mut self, field: impl Into<String>, value: &mut String) -> bool {
+        *value = normalize(value);
+        if value.is_empty() {
+            self.reject(field, ErrorCode::Required, "Is required");
+            return false;
+        }
+        if value.chars().any(char::is_control) {
+            self.reject(field, ErrorCode::InvalidCharacters, "Must not contain control characters");
+            return false;
+        }
+        true
+    }
+
+    pub fn text(&mut self, field: impl Into<String>, value: &mut String, min: usize, max: usize) {
+        let field = field.into();
+        if !self.required(field.clone(), value) {
+            return;
+        }
+
+        let length = value.chars().count();
+        if length < min {
+            self.reject(field, ErrorCode::TooShort, format!("Must be at least {} long", plural(min, "character")));
+        } else if length > max {
+            self.reject(field, ErrorCode::TooLong, format!("Must be at most {} long", plural(max, "character")));
+        }
+    }
+
+    pub fn count(&mut self, field: impl Into<String>, count: usize, min: usize, max: usize) {
+        if count < min {
+            self.reject(field, ErrorCode::TooFew, format!("Must contain at least {}", plural(min, "item")));
+        } else if count > max {
+            self.reject(field, ErrorCode::TooMany, format!("Must contain at most {}", plural(max, "item")));
+        }
+    }
+
+    pub fn range(&mut self, field: impl Into<String>, value: i64, min: i64, max: i64) {
+        if !(min..=max).contains(&value) {
+            self.reject(field, ErrorCode::OutOfRange, format!("Must be between {} and {}", min, max));
+        }
+    }
+
+    pub fn unique<'v>(&mut self, field: &str, values: impl IntoIterator<Item = &'v str>) {
+        let mut seen = HashSet::new();
+        for (index, value) in values.into_iter().enumerate() {
+            if !value.is_empty() && !seen.insert(value.to_lowercase()) {
+                self.reject(format!("{}[{}]", field, index), ErrorCode::Duplicate, "Duplicates an earlier entry");
+            }
+        }
+    }
+
+    pub fn finish(self) -> AppResult<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::ValidationError(self.errors))
+        }
+    }
+}
+
+pub struct ValidatedJson<T>(pub T);
+
+#[axum::async_trait]
+impl<T> FromRequest<AppState> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Json(mut payload) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::BadRequest(ErrorCode::MalformedBody, rejection.body_text()))?;
+
+        let mut validator = Validator::new(&state.config.validation);
+        payload.validate(&mut validator);
+        validator.finish()?;
+
+        Ok(ValidatedJson(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> ValidationConfig {
+        ValidationConfig {
+            username_min_length: 3,
+            username_max_length: 8,
+            display_name_min_length: 1,
+            display_name_max_length: 64,
+            question_max_length: 300,
+            option_max_length: 120,
+            poll_min_options: 2,
+            poll_max_options: 10,
+            organization_name_max_length: 100,
+            token_name_max_length: 64,
+            token_max_lifetime_days: 365,
+        }
+    }
+
+    fn field_errors(result: AppResult<()>) -> Vec<(String, ErrorCode)> {
+        match result {
+            Err(AppError::ValidationError(errors)) => errors.into_iter().map(|e| (e.field, e.code)).collect(),
+            Err(other) => panic!("expected a validation error, got {}", other),
+            Ok(()) => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collects_every_error() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        v.text("username", &mut "ab".to_string(), 3, 8);
+        v.required("display_name", &mut "   ".to_string());
+        v.count("options", 1, 2, 10);
+        v.range("expires_in_days", 0, 1, 365);
+
+        assert_eq!(field_errors(v.finish()), vec![
+            ("username".to_string(), ErrorCode::TooShort),
+            ("display_name".to_string(), ErrorCode::Required),
+            ("options".to_string(), ErrorCode::TooFew),
+            ("expires_in_days".to_string(), ErrorCode::OutOfRange),
+        ]);
+    }
+
+    #[test]
+    fn length_limits_count_characters() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        v.text("username", &mut "ééé".to_string(), 3, 3);
+        v.text("display_name", &mut "abcd".to_string(), 1, 3);
+
+        assert_eq!(field_errors(v.finish()), vec![("display_name".to_string(), ErrorCode::TooLong)]);
+    }
+
+    #[test]
+    fn rejects_control_characters() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        v.text("question", &mut "line\nbreak".to_string(), 1, 300);
+
+        assert_eq!(field_errors(v.finish()), vec![("question".to_string(), ErrorCode::InvalidCharacters)]);
+    }
+
+    #[test]
+    fn unique_ignores_case() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        v.unique("options", ["Yes", "No", "YES", "", ""]);
+
+        assert_eq!(field_errors(v.finish()), vec![("options[2]".to_string(), ErrorCode::Duplicate)]);
+    }
+
+    #[test]
+    fn normalizes_to_trimmed_nfc() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        let mut decomposed = "  Cafe\u{301} ".to_string();
+        v.text("question", &mut decomposed, 1, 4);
+        let mut composed = "Caf\u{e9}".to_string();
+        v.required("option", &mut composed);
+        v.unique("options", [decomposed.as_str(), composed.as_str()]);
+
+        assert_eq!(decomposed, "Caf\u{e9}");
+        assert_eq!(field_errors(v.finish()), vec![("options[1]".to_string(), ErrorCode::Duplicate)]);
+    }
+
+    #[test]
+    fn present_leaves_the_value_untouched() {
+        let limits = limits();
+        let mut v = Validator::new(&limits);
+        assert!(v.present("username", " alice "));
+        assert!(!v.present("username", "  "));
+
+        assert_eq!(field_errors(v.finish()), vec![("username".to_string(), ErrorCode::Required)]);
+    }
+}
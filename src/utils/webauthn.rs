@@ -1,48 +1,33 @@
 use webauthn_rs::prelude::*;
 use std::sync::Arc;
-use std::env;
+use crate::config::WebauthnConfig;
 use crate::utils::error::{AppError, AppResult};
 
-pub fn init_webauthn() -> AppResult<Arc<Webauthn>> {
-    let rp_id = env::var("RP_ID")
-        .unwrap_or_else(|_| {
-            if cfg!(debug_assertions) {
-                "localhost".to_string()
-            } else {
-                eprintln!("RP_ID must be set in .env for production");
-                std::process::exit(1);
-            }
-        });
+pub fn init_webauthn(config: &WebauthnConfig) -> AppResult<Arc<Webauthn>> {
+    let rp_id = &config.rp_id;
+    let rp_origin = &config.rp_origin;
+    let rp_name = &config.rp_name;
     
-    let rp_origin_str = env::var("RP_ORIGIN")
-        .unwrap_or_else(|_| {
-            if cfg!(debug_assertions) {
-                "http://localhost:3000".to_string()
-            } else {
-                eprintln!("RP_ORIGIN must be set in .env for production");
-                std::process::exit(1);
-            }
-        });
-    
-    let rp_origin = Url::parse(&rp_origin_str)
-        .map_err(|e| AppError::WebauthnError(format!("Invalid RP_ORIGIN URL: {}", e)))?;
-    
-    let rp_name = env::var("RP_NAME")
-        .unwrap_or_else(|_| "Polling App".to_string());
-    
-    let mut builder = WebauthnBuilder::new(&rp_id, &rp_origin)
+    let mut builder = WebauthnBuilder::new(rp_id, rp_origin)
         .map_err(|e| AppError::WebauthnError(format!("Invalid configuration: {}", e)))?
-        .rp_name(&rp_name)
-        .allow_subdomains(false);
-    
-    if cfg!(debug_assertions) {
-        builder = builder.allow_any_port(true);
+        .rp_name(rp_name)
+        .allow_subdomains(config.allow_subdomains)
+        .allow_any_port(config.allow_any_port);
+
+    for origin in &config.extra_origins {
+        builder = builder.append_allowed_origin(origin);
     }
     
     let webauthn = builder.build()
         .map_err(|e| AppError::WebauthnError(format!("Failed to build Webauthn: {}", e)))?;
     
-    println!("WebAuthn initialized - RP ID: {}, RP Origin: {}", rp_id, rp_origin_str);
+    tracing::info!(
+        rp_id = %rp_id,
+        rp_origin = %rp_origin,
+        additional_origins = config.extra_origins.len(),
+        allow_subdomains = config.allow_subdomains,
+        "WebAuthn initialized"
+    );
     
     Ok(Arc::new(webauthn))
 }
\ No newline at end of file